@@ -2,10 +2,580 @@
 #![allow(unused_variables)]
 #![allow(unused_mut)]
 use crate::{assign, index_assign, seahorse_util::*};
-use anchor_lang::{prelude::*, solana_program};
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{prelude::*, solana_program, system_program, Discriminator};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        default_account_state::DefaultAccountState, transfer_fee::TransferFeeConfig,
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    state::AccountState,
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 use std::{cell::RefCell, rc::Rc};
 
+// Codes are pinned explicitly so clients can rely on them across upgrades;
+// new variants must take a fresh number rather than reuse or renumber one.
+#[error_code]
+pub enum TokenSwapError {
+    #[msg("Invalid authority")]
+    InvalidAuthority = 0,
+    #[msg("The premium and normal reserves are not related")]
+    ReservesNotRelated = 1,
+    #[msg("Invalid premium token account")]
+    InvalidPremiumTokenAccount = 2,
+    #[msg("Invalid normal token account")]
+    InvalidNormalTokenAccount = 3,
+    #[msg("Premium reserve not live yet")]
+    PremiumReserveNotLive = 4,
+    #[msg("Normal reserve not live yet")]
+    NormalReserveNotLive = 5,
+    #[msg("Token amount too low to swap")]
+    InsufficientLiquidity = 6,
+    #[msg("Premium mint can't be the same as normal mint")]
+    SameMint = 7,
+    #[msg("Creators do not match")]
+    CreatorMismatch = 8,
+    #[msg("Invalid premium mint")]
+    InvalidPremiumMint = 9,
+    #[msg("Invalid normal mint")]
+    InvalidNormalMint = 10,
+    #[msg("Program account missing from the instruction")]
+    MissingProgram = 11,
+    #[msg("PDA bump missing for an initialized account")]
+    MissingBump = 12,
+    #[msg("Exchange rate numerator and denominator must be non-zero")]
+    InvalidRate = 13,
+    #[msg("Arithmetic overflow")]
+    MathOverflow = 14,
+    #[msg("Premium reserve can't link any more normal mints")]
+    NormalMintCapReached = 15,
+    #[msg("Normal mint cap can't be lower than the number of linked normal mints")]
+    NormalMintCapBelowLinked = 16,
+    #[msg("Source token account holds the wrong mint")]
+    InvalidSourceMint = 18,
+    #[msg("Destination token account holds the wrong mint")]
+    InvalidDestinationMint = 19,
+    #[msg("Vault balance doesn't match the reserve's recorded liquidity")]
+    ReserveOutOfBalance = 20,
+    #[msg("Premium reserve still has linked normal reserves")]
+    NormalReservesStillLinked = 21,
+    #[msg("Swaps in this direction are paused")]
+    SwapsPaused = 22,
+    #[msg("Swap output is below the minimum amount out")]
+    SlippageExceeded = 23,
+    #[msg("Swap deadline has passed")]
+    DeadlineExceeded = 24,
+    #[msg("Fee can't exceed 10000 basis points")]
+    InvalidFee = 25,
+    #[msg("No authority transfer is pending for this reserve")]
+    NoPendingAuthority = 26,
+    #[msg("Withdrawal request is still timelocked")]
+    WithdrawalNotReady = 27,
+    #[msg("Liquidity is locked until the reserve's lock end")]
+    LiquidityLocked = 28,
+    #[msg("Withdrawal delay and liquidity lock can only be extended")]
+    TimelockShortened = 29,
+    #[msg("Withdrawal request doesn't match this withdrawal")]
+    WithdrawalRequestMismatch = 31,
+    #[msg("Swap amount is too small to convert without losing precision")]
+    ConversionPrecisionLoss = 32,
+    #[msg("Swaps in this direction have ended")]
+    SwapWindowClosed = 33,
+    #[msg("End and sunset times must follow go-live, in that order")]
+    InvalidSchedule = 34,
+    #[msg("Pause the reserve before moving its schedule in a way that stops live swaps")]
+    ScheduleStopsLiveSwaps = 35,
+    #[msg("random_hash can't be longer than 32 bytes")]
+    RandomHashTooLong = 36,
+    #[msg("Token program doesn't own this reserve's vault")]
+    InvalidTokenProgram = 37,
+    #[msg("Non-transferable mints can't be held in a reserve")]
+    NonTransferableMint = 38,
+    #[msg("Mints with a permanent delegate can't be held in a reserve")]
+    PermanentDelegateMint = 39,
+    #[msg("Mints that freeze new accounts by default can't be held in a reserve")]
+    DefaultFrozenMint = 40,
+    #[msg("Premium reserve must hold the premium mint's mint authority")]
+    MintAuthorityNotReserve = 41,
+    #[msg("Normal reserve isn't backed by wrapped SOL")]
+    NotNativeMint = 42,
+    #[msg("Wrapped SOL can't be burned, so it can't back a MintAndBurn reserve")]
+    NativeMintNotBurnable = 43,
+    #[msg("Cancel or execute the pending withdrawal requests before closing")]
+    WithdrawalsPending = 45,
+    #[msg("Revoke every approved destination except the one the close sweeps to")]
+    DestinationsStillApproved = 46,
+    #[msg("Withdrawal delay is below MIN_WITHDRAWAL_DELAY")]
+    WithdrawalDelayTooShort = 47,
+    #[msg("Mints with a transfer hook can't be held in a reserve")]
+    TransferHookMint = 48,
+}
+
+#[derive(Clone, Debug, PartialEq, AnchorSerialize, AnchorDeserialize, Copy)]
+pub enum RoundingPolicy {
+    Down,
+    Up,
+}
+
+#[derive(Clone, Debug, PartialEq, AnchorSerialize, AnchorDeserialize, Copy)]
+pub enum SwapDirection {
+    PremiumToNormal,
+    NormalToPremium,
+}
+
+// How a premium reserve supplies premium tokens. `Vault` pays out of the
+// pre-funded `premium_account`; the mint modes have the reserve PDA hold
+// the premium mint authority, minting premium on the way out and burning
+// it on the way in. `MintAndBurn` also burns the incoming normal tokens
+// instead of keeping them in the normal vault.
+#[derive(Clone, Debug, PartialEq, AnchorSerialize, AnchorDeserialize, Copy)]
+pub enum SupplyMode {
+    Vault,
+    Mint,
+    MintAndBurn,
+}
+
+#[event]
+pub struct PremiumReserveCreated {
+    pub premium_mint_reserve_acc: Pubkey,
+    pub premium_mint: Pubkey,
+    pub premium_account: Pubkey,
+    pub creator: Pubkey,
+    pub go_live_timestamp: i64,
+    pub normal_mints: u32,
+    pub fee_bps: u16,
+    pub supply_mode: SupplyMode,
+}
+
+#[event]
+pub struct NormalReserveLinked {
+    pub premium_mint_reserve_acc: Pubkey,
+    pub normal_mint_reserve_acc: Pubkey,
+    pub normal_mint: Pubkey,
+    pub normal_token_account: Pubkey,
+    pub go_live_ts: i64,
+    pub rate_numerator: u64,
+    pub rate_denominator: u64,
+}
+
+#[event]
+pub struct Swapped {
+    pub direction: SwapDirection,
+    pub user: Pubkey,
+    pub premium_mint_reserve_acc: Pubkey,
+    pub normal_mint_reserve_acc: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+}
+
+#[event]
+pub struct NormalSwapped {
+    pub user: Pubkey,
+    pub premium_mint_reserve_acc: Pubkey,
+    pub from_normal_mint_reserve_acc: Pubkey,
+    pub to_normal_mint_reserve_acc: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+}
+
+// `normal_mint_reserve_acc` is `None` for movements on the premium vault.
+#[event]
+pub struct Withdrawn {
+    pub premium_mint_reserve_acc: Pubkey,
+    pub normal_mint_reserve_acc: Option<Pubkey>,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct Deposited {
+    pub premium_mint_reserve_acc: Pubkey,
+    pub normal_mint_reserve_acc: Option<Pubkey>,
+    pub mint: Pubkey,
+    pub source: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReserveReconciled {
+    pub premium_mint_reserve_acc: Pubkey,
+    pub normal_mint_reserve_acc: Option<Pubkey>,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+// Converts `amount` by `numerator / denominator`, rounding the remainder as
+// the reserve's policy dictates.
+pub fn convert_amount(
+    amount: u64,
+    numerator: u64,
+    denominator: u64,
+    rounding: RoundingPolicy,
+) -> Result<u64> {
+    let product = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    let mut converted = product
+        .checked_div(denominator as u128)
+        .ok_or(error!(TokenSwapError::InvalidRate))?;
+
+    if rounding == RoundingPolicy::Up && product % (denominator as u128) != 0 {
+        converted = converted
+            .checked_add(1)
+            .ok_or(error!(TokenSwapError::MathOverflow))?;
+    }
+
+    u64::try_from(converted).map_err(|_| error!(TokenSwapError::MathOverflow))
+}
+
+// Largest share of a swap's output, in basis points, that rounding may add or
+// remove before the conversion is rejected.
+pub const MAX_CONVERSION_LOSS_BPS: u128 = 10;
+
+// Converts a raw amount of one mint into raw units of another, given a rate of
+// `numerator / denominator` UI units out per UI unit in. The rate is taken as
+// u128 so composite rates (products of two reserve rates) fit.
+pub fn convert_between_mints(
+    amount: u64,
+    mut numerator: u128,
+    mut denominator: u128,
+    from_decimals: u8,
+    to_decimals: u8,
+    rounding: RoundingPolicy,
+) -> Result<u64> {
+    if to_decimals >= from_decimals {
+        numerator = 10u128
+            .checked_pow((to_decimals - from_decimals) as u32)
+            .and_then(|scale| numerator.checked_mul(scale))
+            .ok_or(error!(TokenSwapError::MathOverflow))?;
+    } else {
+        denominator = 10u128
+            .checked_pow((from_decimals - to_decimals) as u32)
+            .and_then(|scale| denominator.checked_mul(scale))
+            .ok_or(error!(TokenSwapError::MathOverflow))?;
+    }
+
+    let product = (amount as u128)
+        .checked_mul(numerator)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    let mut converted = product
+        .checked_div(denominator)
+        .ok_or(error!(TokenSwapError::InvalidRate))?;
+    let remainder = product % denominator;
+    let mut deviation = remainder;
+
+    if rounding == RoundingPolicy::Up && remainder != 0 {
+        converted = converted
+            .checked_add(1)
+            .ok_or(error!(TokenSwapError::MathOverflow))?;
+        deviation = denominator - remainder;
+    }
+
+    // `deviation / product` is the share of the exact result lost or gained
+    // to rounding; an output rounded to zero loses all of it.
+    if amount > 0 {
+        let within_bound = match (
+            deviation.checked_mul(10_000),
+            product.checked_mul(MAX_CONVERSION_LOSS_BPS),
+        ) {
+            (Some(lost), Some(allowed)) => lost <= allowed,
+            _ => false,
+        };
+
+        if !(converted > 0 && within_bound) {
+            return Err(error!(TokenSwapError::ConversionPrecisionLoss));
+        }
+    }
+
+    u64::try_from(converted).map_err(|_| error!(TokenSwapError::MathOverflow))
+}
+
+// Rate from one normal mint to another through their shared premium mint:
+// `from_denominator / from_numerator` premium per from-unit, times
+// `to_numerator / to_denominator` to-units per premium. Premium decimals
+// cancel out, so only the two normal mints' decimals matter.
+pub fn normal_to_normal_rate(
+    from_numerator: u64,
+    from_denominator: u64,
+    to_numerator: u64,
+    to_denominator: u64,
+) -> (u128, u128) {
+    (
+        from_denominator as u128 * to_numerator as u128,
+        from_numerator as u128 * to_denominator as u128,
+    )
+}
+
+// Rate equivalent to swapping raw amounts 1:1 between mints of the given
+// decimals, as `(numerator, denominator)` in UI units.
+pub fn raw_parity_rate(premium_decimals: u8, normal_decimals: u8) -> Result<(u64, u64)> {
+    if premium_decimals >= normal_decimals {
+        let scale = 10u64
+            .checked_pow((premium_decimals - normal_decimals) as u32)
+            .ok_or(error!(TokenSwapError::InvalidRate))?;
+
+        Ok((scale, 1))
+    } else {
+        let scale = 10u64
+            .checked_pow((normal_decimals - premium_decimals) as u32)
+            .ok_or(error!(TokenSwapError::InvalidRate))?;
+
+        Ok((1, scale))
+    }
+}
+
+// Checks that a reserve's end and sunset times come after go-live and that
+// the sunset doesn't precede the end.
+pub fn check_schedule(go_live: i64, end_ts: Option<i64>, sunset_ts: Option<i64>) -> Result<()> {
+    if let Some(end_ts) = end_ts {
+        if !(end_ts > go_live) {
+            return Err(error!(TokenSwapError::InvalidSchedule));
+        }
+    }
+
+    if let Some(sunset_ts) = sunset_ts {
+        if !(sunset_ts > go_live) {
+            return Err(error!(TokenSwapError::InvalidSchedule));
+        }
+    }
+
+    if let (Some(end_ts), Some(sunset_ts)) = (end_ts, sunset_ts) {
+        if !(sunset_ts >= end_ts) {
+            return Err(error!(TokenSwapError::InvalidSchedule));
+        }
+    }
+
+    Ok(())
+}
+
+// Swaps close at `end_ts`, except in the reserve's grace direction, which
+// stays open until `sunset_ts`.
+pub fn swap_window_open(
+    end_ts: Option<i64>,
+    sunset_ts: Option<i64>,
+    grace_direction: SwapDirection,
+    direction: SwapDirection,
+    now: i64,
+) -> bool {
+    if let Some(sunset_ts) = sunset_ts {
+        if now >= sunset_ts {
+            return false;
+        }
+    }
+
+    match end_ts {
+        Some(end_ts) => direction == grace_direction || now < end_ts,
+        None => true,
+    }
+}
+
+pub fn check_swap_window(
+    end_ts: Option<i64>,
+    sunset_ts: Option<i64>,
+    grace_direction: SwapDirection,
+    direction: SwapDirection,
+    now: i64,
+) -> Result<()> {
+    if !swap_window_open(end_ts, sunset_ts, grace_direction, direction, now) {
+        return Err(error!(TokenSwapError::SwapWindowClosed));
+    }
+
+    Ok(())
+}
+
+// A direction that is swapping right now may only be stopped by a schedule
+// change once it has been paused explicitly.
+#[allow(clippy::too_many_arguments)]
+pub fn check_schedule_keeps_live_swaps(
+    old_go_live: i64,
+    old_end_ts: Option<i64>,
+    old_sunset_ts: Option<i64>,
+    go_live: i64,
+    end_ts: Option<i64>,
+    sunset_ts: Option<i64>,
+    grace_direction: SwapDirection,
+    premium_to_normal_paused: bool,
+    normal_to_premium_paused: bool,
+    now: i64,
+) -> Result<()> {
+    for (direction, paused) in [
+        (SwapDirection::PremiumToNormal, premium_to_normal_paused),
+        (SwapDirection::NormalToPremium, normal_to_premium_paused),
+    ] {
+        let open_before = old_go_live < now
+            && swap_window_open(old_end_ts, old_sunset_ts, grace_direction, direction, now);
+        let open_after =
+            go_live < now && swap_window_open(end_ts, sunset_ts, grace_direction, direction, now);
+
+        if !(paused || !open_before || open_after) {
+            return Err(error!(TokenSwapError::ScheduleStopsLiveSwaps));
+        }
+    }
+
+    Ok(())
+}
+
+// `random_hash` is a PDA seed, and seeds are capped at 32 bytes.
+pub const MAX_RANDOM_HASH_LEN: usize = 32;
+
+// Validates `random_hash` before it is used to derive a new premium reserve,
+// so an oversized value fails with a clear error rather than in the runtime.
+pub fn random_hash_seed(random_hash: &str) -> Result<&[u8]> {
+    if !(random_hash.len() <= MAX_RANDOM_HASH_LEN) {
+        return Err(error!(TokenSwapError::RandomHashTooLong));
+    }
+
+    Ok(random_hash.as_bytes())
+}
+
+// Fee in basis points taken out of a swap's output, rounded down.
+pub fn swap_fee(amount_out: u64, fee_bps: u16) -> Result<u64> {
+    if !(fee_bps <= 10_000) {
+        return Err(error!(TokenSwapError::InvalidFee));
+    }
+
+    convert_amount(amount_out, fee_bps as u64, 10_000, RoundingPolicy::Down)
+}
+
+// What a transfer actually credited to `vault`. Token-2022 transfer fees are
+// withheld from the recipient, so the vault can gain less than was sent; its
+// data is re-read because the loaded account predates the transfer.
+pub fn amount_received(vault: &AccountInfo, balance_before: u64) -> Result<u64> {
+    let balance_after = TokenAccount::try_deserialize(&mut &vault.try_borrow_data()?[..])?.amount;
+
+    balance_after
+        .checked_sub(balance_before)
+        .ok_or(error!(TokenSwapError::MathOverflow))
+}
+
+// Transfers `amount` from `source` into `vault` and adds what the vault
+// actually received, net of any Token-2022 transfer fee, to `counter`. Swaps
+// pull their input in first so the output is priced on that net amount.
+pub fn pull_into_vault<'info>(
+    token_program: AccountInfo<'info>,
+    source: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    vault: &SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mint: &SeahorseInterfaceAccount<'info, '_, Mint>,
+    amount: u64,
+    counter: &mut u64,
+) -> Result<u64> {
+    let balance_before = vault.amount;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program,
+            token_interface::TransferChecked {
+                from: source,
+                mint: mint.to_account_info(),
+                authority,
+                to: vault.to_account_info(),
+            },
+        ),
+        amount,
+        mint.decimals,
+    )?;
+
+    let amount_in = amount_received(&vault.to_account_info(), balance_before)?;
+
+    *counter = counter
+        .checked_add(amount_in)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    Ok(amount_in)
+}
+
+// Fee a Token-2022 mint withholds from the recipient when `amount` is
+// transferred during `epoch`; zero for SPL Token mints and mints without a
+// transfer fee.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64, epoch: u64) -> Result<u64> {
+    if !(*mint.owner == spl_token_2022::ID) {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(epoch, amount)
+            .ok_or(error!(TokenSwapError::MathOverflow)),
+        Err(_) => Ok(0),
+    }
+}
+
+// spl-token-2022 0.6 predates the transfer hook extension, so `ExtensionType`
+// can't name it and fails to decode any mint that carries it.
+pub const TRANSFER_HOOK_EXTENSION: u16 = 14;
+
+// Walks the raw TLV entries after the base mint and its account-type byte,
+// returning each extension's type tag whether or not this crate knows it.
+fn raw_extension_types(data: &[u8]) -> Vec<u16> {
+    let mut types = vec![];
+    let mut start = spl_token_2022::state::Account::LEN + 1;
+
+    while start + 4 <= data.len() {
+        let extension_type = u16::from_le_bytes([data[start], data[start + 1]]);
+
+        if extension_type == 0 {
+            break;
+        }
+
+        let length = u16::from_le_bytes([data[start + 2], data[start + 3]]) as usize;
+
+        types.push(extension_type);
+        start += 4 + length;
+    }
+
+    types
+}
+
+// Rejects Token-2022 mints with extensions a reserve can't honor: vaults
+// couldn't pay out non-transferable tokens, a permanent delegate could drain
+// them, a transfer hook could run arbitrary code on (or block) every vault
+// transfer, and a vault frozen on creation couldn't move anything.
+pub fn check_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if !(*mint.owner == spl_token_2022::ID) {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+
+    if raw_extension_types(&data).contains(&TRANSFER_HOOK_EXTENSION) {
+        return Err(error!(TokenSwapError::TransferHookMint));
+    }
+
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    for extension in mint_state.get_extension_types()? {
+        match extension {
+            ExtensionType::NonTransferable => {
+                return Err(error!(TokenSwapError::NonTransferableMint));
+            }
+            ExtensionType::PermanentDelegate => {
+                return Err(error!(TokenSwapError::PermanentDelegateMint));
+            }
+            ExtensionType::DefaultAccountState => {
+                let default_state = mint_state.get_extension::<DefaultAccountState>()?;
+
+                if default_state.state == AccountState::Frozen as u8 {
+                    return Err(error!(TokenSwapError::DefaultFrozenMint));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
 #[account]
 #[derive(Debug)]
 pub struct NormalMintReserve {
@@ -16,6 +586,52 @@ pub struct NormalMintReserve {
     pub initialization_ts: i64,
     pub bump: u8,
     pub token_bump: u8,
+    pub rate_numerator: u64,
+    pub rate_denominator: u64,
+    pub rounding: RoundingPolicy,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_swapped_in: u64,
+    pub total_swapped_out: u64,
+    pub premium_to_normal_paused: bool,
+    pub normal_to_premium_paused: bool,
+    pub fee_bps_override: Option<u16>,
+    pub premium_decimals: u8,
+    pub normal_decimals: u8,
+    pub end_ts: Option<i64>,
+    pub sunset_ts: Option<i64>,
+    pub grace_direction: SwapDirection,
+    pub initialization_slot: u64,
+    pub normal_token_program: Pubkey,
+}
+
+impl NormalMintReserve {
+    // Serialized size of every field plus the 8-byte account discriminator.
+    pub const SPACE: usize = 8
+        + 32 // premium_mint_reserve_acc
+        + 32 // normal_mint
+        + 32 // normal_token_account
+        + 8 // go_live_ts
+        + 8 // initialization_ts
+        + 1 // bump
+        + 1 // token_bump
+        + 8 // rate_numerator
+        + 8 // rate_denominator
+        + 1 // rounding
+        + 8 // total_deposited
+        + 8 // total_withdrawn
+        + 8 // total_swapped_in
+        + 8 // total_swapped_out
+        + 1 // premium_to_normal_paused
+        + 1 // normal_to_premium_paused
+        + 3 // fee_bps_override
+        + 1 // premium_decimals
+        + 1 // normal_decimals
+        + 9 // end_ts
+        + 9 // sunset_ts
+        + 1 // grace_direction
+        + 8 // initialization_slot
+        + 32; // normal_token_program
 }
 
 impl<'info, 'entrypoint> NormalMintReserve {
@@ -30,6 +646,23 @@ impl<'info, 'entrypoint> NormalMintReserve {
         let initialization_ts = account.initialization_ts;
         let bump = account.bump;
         let token_bump = account.token_bump;
+        let rate_numerator = account.rate_numerator;
+        let rate_denominator = account.rate_denominator;
+        let rounding = account.rounding.clone();
+        let total_deposited = account.total_deposited;
+        let total_withdrawn = account.total_withdrawn;
+        let total_swapped_in = account.total_swapped_in;
+        let total_swapped_out = account.total_swapped_out;
+        let premium_to_normal_paused = account.premium_to_normal_paused;
+        let normal_to_premium_paused = account.normal_to_premium_paused;
+        let fee_bps_override = account.fee_bps_override;
+        let premium_decimals = account.premium_decimals;
+        let normal_decimals = account.normal_decimals;
+        let end_ts = account.end_ts.clone();
+        let sunset_ts = account.sunset_ts.clone();
+        let grace_direction = account.grace_direction.clone();
+        let initialization_slot = account.initialization_slot;
+        let normal_token_program = account.normal_token_program.clone();
 
         Mutable::new(LoadedNormalMintReserve {
             __account__: account,
@@ -41,6 +674,23 @@ impl<'info, 'entrypoint> NormalMintReserve {
             initialization_ts,
             bump,
             token_bump,
+            rate_numerator,
+            rate_denominator,
+            rounding,
+            total_deposited,
+            total_withdrawn,
+            total_swapped_in,
+            total_swapped_out,
+            premium_to_normal_paused,
+            normal_to_premium_paused,
+            fee_bps_override,
+            premium_decimals,
+            normal_decimals,
+            end_ts,
+            sunset_ts,
+            grace_direction,
+            initialization_slot,
+            normal_token_program,
         })
     }
 
@@ -73,6 +723,74 @@ impl<'info, 'entrypoint> NormalMintReserve {
         let token_bump = loaded.token_bump;
 
         loaded.__account__.token_bump = token_bump;
+
+        let rate_numerator = loaded.rate_numerator;
+
+        loaded.__account__.rate_numerator = rate_numerator;
+
+        let rate_denominator = loaded.rate_denominator;
+
+        loaded.__account__.rate_denominator = rate_denominator;
+
+        let rounding = loaded.rounding.clone();
+
+        loaded.__account__.rounding = rounding;
+
+        let total_deposited = loaded.total_deposited;
+
+        loaded.__account__.total_deposited = total_deposited;
+
+        let total_withdrawn = loaded.total_withdrawn;
+
+        loaded.__account__.total_withdrawn = total_withdrawn;
+
+        let total_swapped_in = loaded.total_swapped_in;
+
+        loaded.__account__.total_swapped_in = total_swapped_in;
+
+        let total_swapped_out = loaded.total_swapped_out;
+
+        loaded.__account__.total_swapped_out = total_swapped_out;
+
+        let premium_to_normal_paused = loaded.premium_to_normal_paused;
+
+        loaded.__account__.premium_to_normal_paused = premium_to_normal_paused;
+
+        let normal_to_premium_paused = loaded.normal_to_premium_paused;
+
+        loaded.__account__.normal_to_premium_paused = normal_to_premium_paused;
+
+        let fee_bps_override = loaded.fee_bps_override;
+
+        loaded.__account__.fee_bps_override = fee_bps_override;
+
+        let premium_decimals = loaded.premium_decimals;
+
+        loaded.__account__.premium_decimals = premium_decimals;
+
+        let normal_decimals = loaded.normal_decimals;
+
+        loaded.__account__.normal_decimals = normal_decimals;
+
+        let end_ts = loaded.end_ts.clone();
+
+        loaded.__account__.end_ts = end_ts;
+
+        let sunset_ts = loaded.sunset_ts.clone();
+
+        loaded.__account__.sunset_ts = sunset_ts;
+
+        let grace_direction = loaded.grace_direction.clone();
+
+        loaded.__account__.grace_direction = grace_direction;
+
+        let initialization_slot = loaded.initialization_slot;
+
+        loaded.__account__.initialization_slot = initialization_slot;
+
+        let normal_token_program = loaded.normal_token_program.clone();
+
+        loaded.__account__.normal_token_program = normal_token_program;
     }
 }
 
@@ -87,6 +805,58 @@ pub struct LoadedNormalMintReserve<'info, 'entrypoint> {
     pub initialization_ts: i64,
     pub bump: u8,
     pub token_bump: u8,
+    pub rate_numerator: u64,
+    pub rate_denominator: u64,
+    pub rounding: RoundingPolicy,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_swapped_in: u64,
+    pub total_swapped_out: u64,
+    pub premium_to_normal_paused: bool,
+    pub normal_to_premium_paused: bool,
+    pub fee_bps_override: Option<u16>,
+    pub premium_decimals: u8,
+    pub normal_decimals: u8,
+    pub end_ts: Option<i64>,
+    pub sunset_ts: Option<i64>,
+    pub grace_direction: SwapDirection,
+    pub initialization_slot: u64,
+    pub normal_token_program: Pubkey,
+}
+
+// Layout of `NormalMintReserve` accounts created under the constant
+// "normal-mint-reserve" seed. Only read by `migrate_normal_mint_reserve`.
+#[derive(Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyNormalMintReserve {
+    pub premium_mint_reserve_acc: Pubkey,
+    pub normal_mint: Pubkey,
+    pub normal_token_account: Pubkey,
+    pub go_live_ts: i64,
+    pub initialization_ts: i64,
+    pub bump: u8,
+    pub token_bump: u8,
+}
+
+impl LegacyNormalMintReserve {
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        load_legacy(account, NormalMintReserve::discriminator())
+    }
+}
+
+// Deserializes an account written by an earlier version of this program,
+// after checking it is ours and carries the expected discriminator.
+fn load_legacy<T: AnchorDeserialize>(account: &AccountInfo, discriminator: [u8; 8]) -> Result<T> {
+    if account.owner != &crate::ID {
+        return Err(error!(ErrorCode::AccountOwnedByWrongProgram));
+    }
+
+    let data = account.try_borrow_data()?;
+
+    if data.len() < 8 || data[..8] != discriminator {
+        return Err(error!(ErrorCode::AccountDiscriminatorMismatch));
+    }
+
+    T::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
 }
 
 #[account]
@@ -101,6 +871,63 @@ pub struct PremiumMintReserve {
     pub random_hash: String,
     pub bump: u8,
     pub token_bump: u8,
+    pub linked_normal_mints: u32,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_swapped_in: u64,
+    pub total_swapped_out: u64,
+    pub pause_authority: Pubkey,
+    pub premium_to_normal_paused: bool,
+    pub normal_to_premium_paused: bool,
+    pub fee_bps: u16,
+    pub pending_creator: Option<Pubkey>,
+    pub treasurer: Pubkey,
+    pub withdrawal_delay: i64,
+    pub liquidity_locked_until: i64,
+    pub end_ts: Option<i64>,
+    pub sunset_ts: Option<i64>,
+    pub grace_direction: SwapDirection,
+    pub initialization_slot: u64,
+    pub premium_token_program: Pubkey,
+    pub supply_mode: SupplyMode,
+    pub destination_epoch: u32,
+    pub open_withdrawal_requests: u32,
+    pub approved_destinations: u32,
+}
+
+impl PremiumMintReserve {
+    pub const SPACE: usize = 8
+        + 32 // premium_mint
+        + 32 // premium_account
+        + 8 // go_live_timestamp
+        + 8 // initialization_timestamp
+        + 4 // normal_mints
+        + 32 // creator
+        + 4 + MAX_RANDOM_HASH_LEN // random_hash
+        + 1 // bump
+        + 1 // token_bump
+        + 4 // linked_normal_mints
+        + 8 // total_deposited
+        + 8 // total_withdrawn
+        + 8 // total_swapped_in
+        + 8 // total_swapped_out
+        + 32 // pause_authority
+        + 1 // premium_to_normal_paused
+        + 1 // normal_to_premium_paused
+        + 2 // fee_bps
+        + 33 // pending_creator
+        + 32 // treasurer
+        + 8 // withdrawal_delay
+        + 8 // liquidity_locked_until
+        + 9 // end_ts
+        + 9 // sunset_ts
+        + 1 // grace_direction
+        + 8 // initialization_slot
+        + 32 // premium_token_program
+        + 1 // supply_mode
+        + 4 // destination_epoch
+        + 4 // open_withdrawal_requests
+        + 4; // approved_destinations
 }
 
 impl<'info, 'entrypoint> PremiumMintReserve {
@@ -117,6 +944,28 @@ impl<'info, 'entrypoint> PremiumMintReserve {
         let random_hash = account.random_hash.clone();
         let bump = account.bump;
         let token_bump = account.token_bump;
+        let linked_normal_mints = account.linked_normal_mints;
+        let total_deposited = account.total_deposited;
+        let total_withdrawn = account.total_withdrawn;
+        let total_swapped_in = account.total_swapped_in;
+        let total_swapped_out = account.total_swapped_out;
+        let pause_authority = account.pause_authority.clone();
+        let premium_to_normal_paused = account.premium_to_normal_paused;
+        let normal_to_premium_paused = account.normal_to_premium_paused;
+        let fee_bps = account.fee_bps;
+        let pending_creator = account.pending_creator.clone();
+        let treasurer = account.treasurer.clone();
+        let withdrawal_delay = account.withdrawal_delay;
+        let liquidity_locked_until = account.liquidity_locked_until;
+        let end_ts = account.end_ts.clone();
+        let sunset_ts = account.sunset_ts.clone();
+        let grace_direction = account.grace_direction.clone();
+        let initialization_slot = account.initialization_slot;
+        let premium_token_program = account.premium_token_program.clone();
+        let supply_mode = account.supply_mode;
+        let destination_epoch = account.destination_epoch;
+        let open_withdrawal_requests = account.open_withdrawal_requests;
+        let approved_destinations = account.approved_destinations;
 
         Mutable::new(LoadedPremiumMintReserve {
             __account__: account,
@@ -130,6 +979,28 @@ impl<'info, 'entrypoint> PremiumMintReserve {
             random_hash,
             bump,
             token_bump,
+            linked_normal_mints,
+            total_deposited,
+            total_withdrawn,
+            total_swapped_in,
+            total_swapped_out,
+            pause_authority,
+            premium_to_normal_paused,
+            normal_to_premium_paused,
+            fee_bps,
+            pending_creator,
+            treasurer,
+            withdrawal_delay,
+            liquidity_locked_until,
+            end_ts,
+            sunset_ts,
+            grace_direction,
+            initialization_slot,
+            premium_token_program,
+            supply_mode,
+            destination_epoch,
+            open_withdrawal_requests,
+            approved_destinations,
         })
     }
 
@@ -170,6 +1041,94 @@ impl<'info, 'entrypoint> PremiumMintReserve {
         let token_bump = loaded.token_bump;
 
         loaded.__account__.token_bump = token_bump;
+
+        let linked_normal_mints = loaded.linked_normal_mints;
+
+        loaded.__account__.linked_normal_mints = linked_normal_mints;
+
+        let total_deposited = loaded.total_deposited;
+
+        loaded.__account__.total_deposited = total_deposited;
+
+        let total_withdrawn = loaded.total_withdrawn;
+
+        loaded.__account__.total_withdrawn = total_withdrawn;
+
+        let total_swapped_in = loaded.total_swapped_in;
+
+        loaded.__account__.total_swapped_in = total_swapped_in;
+
+        let total_swapped_out = loaded.total_swapped_out;
+
+        loaded.__account__.total_swapped_out = total_swapped_out;
+
+        let pause_authority = loaded.pause_authority.clone();
+
+        loaded.__account__.pause_authority = pause_authority;
+
+        let premium_to_normal_paused = loaded.premium_to_normal_paused;
+
+        loaded.__account__.premium_to_normal_paused = premium_to_normal_paused;
+
+        let normal_to_premium_paused = loaded.normal_to_premium_paused;
+
+        loaded.__account__.normal_to_premium_paused = normal_to_premium_paused;
+
+        let fee_bps = loaded.fee_bps;
+
+        loaded.__account__.fee_bps = fee_bps;
+
+        let pending_creator = loaded.pending_creator.clone();
+
+        loaded.__account__.pending_creator = pending_creator;
+
+        let treasurer = loaded.treasurer.clone();
+
+        loaded.__account__.treasurer = treasurer;
+
+        let withdrawal_delay = loaded.withdrawal_delay;
+
+        loaded.__account__.withdrawal_delay = withdrawal_delay;
+
+        let liquidity_locked_until = loaded.liquidity_locked_until;
+
+        loaded.__account__.liquidity_locked_until = liquidity_locked_until;
+
+        let end_ts = loaded.end_ts.clone();
+
+        loaded.__account__.end_ts = end_ts;
+
+        let sunset_ts = loaded.sunset_ts.clone();
+
+        loaded.__account__.sunset_ts = sunset_ts;
+
+        let grace_direction = loaded.grace_direction.clone();
+
+        loaded.__account__.grace_direction = grace_direction;
+
+        let initialization_slot = loaded.initialization_slot;
+
+        loaded.__account__.initialization_slot = initialization_slot;
+
+        let premium_token_program = loaded.premium_token_program.clone();
+
+        loaded.__account__.premium_token_program = premium_token_program;
+
+        let supply_mode = loaded.supply_mode;
+
+        loaded.__account__.supply_mode = supply_mode;
+
+        let destination_epoch = loaded.destination_epoch;
+
+        loaded.__account__.destination_epoch = destination_epoch;
+
+        let open_withdrawal_requests = loaded.open_withdrawal_requests;
+
+        loaded.__account__.open_withdrawal_requests = open_withdrawal_requests;
+
+        let approved_destinations = loaded.approved_destinations;
+
+        loaded.__account__.approved_destinations = approved_destinations;
     }
 }
 
@@ -186,291 +1145,1445 @@ pub struct LoadedPremiumMintReserve<'info, 'entrypoint> {
     pub random_hash: String,
     pub bump: u8,
     pub token_bump: u8,
+    pub linked_normal_mints: u32,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_swapped_in: u64,
+    pub total_swapped_out: u64,
+    pub pause_authority: Pubkey,
+    pub premium_to_normal_paused: bool,
+    pub normal_to_premium_paused: bool,
+    pub fee_bps: u16,
+    pub pending_creator: Option<Pubkey>,
+    pub treasurer: Pubkey,
+    pub withdrawal_delay: i64,
+    pub liquidity_locked_until: i64,
+    pub end_ts: Option<i64>,
+    pub sunset_ts: Option<i64>,
+    pub grace_direction: SwapDirection,
+    pub initialization_slot: u64,
+    pub premium_token_program: Pubkey,
+    pub supply_mode: SupplyMode,
+    pub destination_epoch: u32,
+    pub open_withdrawal_requests: u32,
+    pub approved_destinations: u32,
 }
 
-pub fn withdraw_normal_tokens_handler<'info>(
-    mut authority: SeahorseSigner<'info, '_>,
-    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
-    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
-    mut normal_token_account: SeahorseAccount<'info, '_, TokenAccount>,
-    mut destination: SeahorseAccount<'info, '_, TokenAccount>,
-    mut amount: u64,
-) -> () {
-    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
-        panic!("Invalid authority");
-    }
+// Layout of `PremiumMintReserve` accounts created before per-reserve seeds.
+// Only read by `migrate_premium_account`, which upgrades them in place.
+#[derive(Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyPremiumMintReserve {
+    pub premium_mint: Pubkey,
+    pub premium_account: Pubkey,
+    pub go_live_timestamp: i64,
+    pub initialization_timestamp: i64,
+    pub normal_mints: u32,
+    pub creator: Pubkey,
+    pub random_hash: String,
+    pub bump: u8,
+    pub token_bump: u8,
+}
 
-    if !(normal_mint_reserve_acc.borrow().premium_mint_reserve_acc
-        == premium_mint_reserve_acc.borrow().__account__.key())
-    {
-        panic!("The normal reserve and the premium reserve are not related");
+impl LegacyPremiumMintReserve {
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        load_legacy(account, PremiumMintReserve::discriminator())
     }
 
-    if !(normal_mint_reserve_acc.borrow().normal_token_account == normal_token_account.key()) {
-        panic!("Invalid normal_token account");
+    // Fields added since the legacy layout start from their creation defaults;
+    // the linked count is rebuilt as normal reserves are migrated.
+    pub fn upgrade(self) -> PremiumMintReserve {
+        PremiumMintReserve {
+            premium_mint: self.premium_mint,
+            premium_account: self.premium_account,
+            go_live_timestamp: self.go_live_timestamp,
+            initialization_timestamp: self.initialization_timestamp,
+            normal_mints: self.normal_mints,
+            creator: self.creator,
+            random_hash: self.random_hash,
+            bump: self.bump,
+            token_bump: self.token_bump,
+            linked_normal_mints: 0,
+            total_deposited: 0,
+            total_withdrawn: 0,
+            total_swapped_in: 0,
+            total_swapped_out: 0,
+            pause_authority: self.creator,
+            premium_to_normal_paused: false,
+            normal_to_premium_paused: false,
+            fee_bps: 0,
+            pending_creator: None,
+            treasurer: self.creator,
+            withdrawal_delay: 0,
+            liquidity_locked_until: 0,
+            end_ts: None,
+            sunset_ts: None,
+            grace_direction: SwapDirection::PremiumToNormal,
+            initialization_slot: 0,
+            // The old program only accepted SPL Token accounts.
+            premium_token_program: anchor_spl::token::ID,
+            supply_mode: SupplyMode::Vault,
+            destination_epoch: 0,
+            open_withdrawal_requests: 0,
+            approved_destinations: 0,
+        }
     }
+}
 
-    token::transfer(
-        CpiContext::new(
-            normal_token_account.programs.get("token_program"),
-            token::Transfer {
+// Marks `destination` as a token account the treasurer may withdraw to.
+// `epoch` is part of the seed, so bumping the reserve's `destination_epoch`
+// invalidates every approval made before it.
+#[account]
+#[derive(Debug)]
+pub struct ApprovedDestination {
+    pub premium_mint_reserve_acc: Pubkey,
+    pub destination: Pubkey,
+    pub bump: u8,
+    pub epoch: u32,
+}
+
+impl ApprovedDestination {
+    pub const SPACE: usize = 8
+        + 32 // premium_mint_reserve_acc
+        + 32 // destination
+        + 1 // bump
+        + 4; // epoch
+}
+
+impl<'info, 'entrypoint> ApprovedDestination {
+    pub fn load(
+        account: &'entrypoint mut Box<Account<'info, Self>>,
+        programs_map: &'entrypoint ProgramsMap<'info>,
+    ) -> Mutable<LoadedApprovedDestination<'info, 'entrypoint>> {
+        let premium_mint_reserve_acc = account.premium_mint_reserve_acc.clone();
+        let destination = account.destination.clone();
+        let bump = account.bump;
+        let epoch = account.epoch;
+
+        Mutable::new(LoadedApprovedDestination {
+            __account__: account,
+            __programs__: programs_map,
+            premium_mint_reserve_acc,
+            destination,
+            bump,
+            epoch,
+        })
+    }
+
+    pub fn store(loaded: Mutable<LoadedApprovedDestination>) {
+        let mut loaded = loaded.borrow_mut();
+        let premium_mint_reserve_acc = loaded.premium_mint_reserve_acc.clone();
+
+        loaded.__account__.premium_mint_reserve_acc = premium_mint_reserve_acc;
+
+        let destination = loaded.destination.clone();
+
+        loaded.__account__.destination = destination;
+
+        let bump = loaded.bump;
+
+        loaded.__account__.bump = bump;
+
+        let epoch = loaded.epoch;
+
+        loaded.__account__.epoch = epoch;
+    }
+}
+
+#[derive(Debug)]
+pub struct LoadedApprovedDestination<'info, 'entrypoint> {
+    pub __account__: &'entrypoint mut Box<Account<'info, ApprovedDestination>>,
+    pub __programs__: &'entrypoint ProgramsMap<'info>,
+    pub premium_mint_reserve_acc: Pubkey,
+    pub destination: Pubkey,
+    pub bump: u8,
+    pub epoch: u32,
+}
+
+// A pending withdrawal of `amount` from `vault` to `destination`, executable
+// once `executable_ts` has passed.
+#[account]
+#[derive(Debug)]
+pub struct WithdrawalRequest {
+    pub premium_mint_reserve_acc: Pubkey,
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executable_ts: i64,
+    pub bump: u8,
+}
+
+impl WithdrawalRequest {
+    pub const SPACE: usize = 8
+        + 32 // premium_mint_reserve_acc
+        + 32 // vault
+        + 32 // destination
+        + 8 // amount
+        + 8 // executable_ts
+        + 1; // bump
+}
+
+impl<'info, 'entrypoint> WithdrawalRequest {
+    pub fn load(
+        account: &'entrypoint mut Box<Account<'info, Self>>,
+        programs_map: &'entrypoint ProgramsMap<'info>,
+    ) -> Mutable<LoadedWithdrawalRequest<'info, 'entrypoint>> {
+        let premium_mint_reserve_acc = account.premium_mint_reserve_acc.clone();
+        let vault = account.vault.clone();
+        let destination = account.destination.clone();
+        let amount = account.amount;
+        let executable_ts = account.executable_ts;
+        let bump = account.bump;
+
+        Mutable::new(LoadedWithdrawalRequest {
+            __account__: account,
+            __programs__: programs_map,
+            premium_mint_reserve_acc,
+            vault,
+            destination,
+            amount,
+            executable_ts,
+            bump,
+        })
+    }
+
+    pub fn store(loaded: Mutable<LoadedWithdrawalRequest>) {
+        let mut loaded = loaded.borrow_mut();
+        let premium_mint_reserve_acc = loaded.premium_mint_reserve_acc.clone();
+
+        loaded.__account__.premium_mint_reserve_acc = premium_mint_reserve_acc;
+
+        let vault = loaded.vault.clone();
+
+        loaded.__account__.vault = vault;
+
+        let destination = loaded.destination.clone();
+
+        loaded.__account__.destination = destination;
+
+        let amount = loaded.amount;
+
+        loaded.__account__.amount = amount;
+
+        let executable_ts = loaded.executable_ts;
+
+        loaded.__account__.executable_ts = executable_ts;
+
+        let bump = loaded.bump;
+
+        loaded.__account__.bump = bump;
+    }
+}
+
+#[derive(Debug)]
+pub struct LoadedWithdrawalRequest<'info, 'entrypoint> {
+    pub __account__: &'entrypoint mut Box<Account<'info, WithdrawalRequest>>,
+    pub __programs__: &'entrypoint ProgramsMap<'info>,
+    pub premium_mint_reserve_acc: Pubkey,
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executable_ts: i64,
+    pub bump: u8,
+}
+
+// Shortest notice, in seconds, a reserve may give before a withdrawal request
+// becomes executable. Without it a request could be executed in the slot it
+// was made in.
+pub const MIN_WITHDRAWAL_DELAY: i64 = 24 * 60 * 60;
+
+pub fn check_withdrawal_delay(withdrawal_delay: i64) -> Result<()> {
+    if !(withdrawal_delay >= MIN_WITHDRAWAL_DELAY) {
+        return Err(error!(TokenSwapError::WithdrawalDelayTooShort));
+    }
+
+    Ok(())
+}
+
+// Shared by both withdrawal paths: the request must be due and the reserve
+// must not be in locked-liquidity mode.
+pub fn check_withdrawal_allowed(
+    premium_mint_reserve_acc: &LoadedPremiumMintReserve,
+    withdrawal_request: &LoadedWithdrawalRequest,
+    now: i64,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.liquidity_locked_until <= now) {
+        return Err(error!(TokenSwapError::LiquidityLocked));
+    }
+
+    if !(withdrawal_request.executable_ts <= now) {
+        return Err(error!(TokenSwapError::WithdrawalNotReady));
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_normal_tokens_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut normal_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut withdrawal_request: Mutable<LoadedWithdrawalRequest<'info, '_>>,
+    mut clock: Sysvar<'info, Clock>,
+) -> Result<Withdrawn> {
+    if !(premium_mint_reserve_acc.borrow().treasurer == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    check_withdrawal_allowed(
+        &premium_mint_reserve_acc.borrow(),
+        &withdrawal_request.borrow(),
+        clock.unix_timestamp,
+    )?;
+
+    let mut amount = withdrawal_request.borrow().amount;
+
+    // The request is closed once it has been executed.
+    let open_withdrawal_requests = premium_mint_reserve_acc
+        .borrow()
+        .open_withdrawal_requests
+        .checked_sub(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc
+            .borrow_mut()
+            .open_withdrawal_requests,
+        open_withdrawal_requests
+    );
+
+    let premium_mint_reserve_key = normal_mint_reserve_acc.borrow().premium_mint_reserve_acc;
+    let normal_mint_key = normal_mint_reserve_acc.borrow().normal_mint;
+    let bump = normal_mint_reserve_acc.borrow().bump;
+    let signer_seeds: &[&[u8]] = &[
+        "normal-mint-reserve".as_bytes(),
+        premium_mint_reserve_key.as_ref(),
+        normal_mint_key.as_ref(),
+        &[bump],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            normal_token_account.programs.get("token_program")?,
+            token_interface::TransferChecked {
                 from: normal_token_account.to_account_info(),
+                mint: normal_mint.to_account_info(),
                 authority: normal_mint_reserve_acc
                     .borrow()
                     .__account__
                     .to_account_info(),
                 to: destination.to_account_info(),
             },
+            &[signer_seeds],
         ),
         amount,
-    )
-    .unwrap();
+        normal_mint.decimals,
+    )?;
+
+    let total_withdrawn = normal_mint_reserve_acc
+        .borrow()
+        .total_withdrawn
+        .checked_add(amount)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().total_withdrawn,
+        total_withdrawn
+    );
+
+    let event = Withdrawn {
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        normal_mint_reserve_acc: Some(normal_mint_reserve_acc.borrow().__account__.key()),
+        mint: normal_mint_reserve_acc.borrow().normal_mint,
+        destination: destination.key(),
+        amount,
+    };
+
+    Ok(event)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn swap_normal_tokens_for_premium_tokens_handler<'info>(
     mut source_authority: SeahorseSigner<'info, '_>,
     mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
     mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
-    mut normal_token_account: SeahorseAccount<'info, '_, TokenAccount>,
-    mut premium_account: SeahorseAccount<'info, '_, TokenAccount>,
-    mut source: SeahorseAccount<'info, '_, TokenAccount>,
-    mut destination: SeahorseAccount<'info, '_, TokenAccount>,
+    mut normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut source: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut fee_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut normal_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
     mut clock: Sysvar<'info, Clock>,
     mut amount: u64,
-) -> () {
-    if !(premium_mint_reserve_acc.borrow().premium_account == premium_account.key()) {
-        panic!("Invalid premium token account");
+    mut min_amount_out: u64,
+    mut deadline: Option<i64>,
+) -> Result<Swapped> {
+    if let Some(deadline) = deadline {
+        if !(clock.unix_timestamp <= deadline) {
+            return Err(error!(TokenSwapError::DeadlineExceeded));
+        }
     }
 
-    if !(normal_mint_reserve_acc.borrow().premium_mint_reserve_acc
-        == premium_mint_reserve_acc.borrow().__account__.key())
+    if premium_mint_reserve_acc.borrow().normal_to_premium_paused
+        || normal_mint_reserve_acc.borrow().normal_to_premium_paused
     {
-        panic!("The premium and normal reserves are not related");
-    }
-
-    if !(normal_mint_reserve_acc.borrow().normal_token_account == normal_token_account.key()) {
-        panic!("Invalid child_storage");
+        return Err(error!(TokenSwapError::SwapsPaused));
     }
 
     if !(premium_mint_reserve_acc.borrow().go_live_timestamp < clock.unix_timestamp) {
-        panic!("Premium reserve not live yet");
+        return Err(error!(TokenSwapError::PremiumReserveNotLive));
     }
 
     if !(normal_mint_reserve_acc.borrow().go_live_ts < clock.unix_timestamp) {
-        panic!("Normal reserve not live yet");
+        return Err(error!(TokenSwapError::NormalReserveNotLive));
+    }
+
+    check_swap_window(
+        premium_mint_reserve_acc.borrow().end_ts,
+        premium_mint_reserve_acc.borrow().sunset_ts,
+        premium_mint_reserve_acc.borrow().grace_direction,
+        SwapDirection::NormalToPremium,
+        clock.unix_timestamp,
+    )?;
+
+    check_swap_window(
+        normal_mint_reserve_acc.borrow().end_ts,
+        normal_mint_reserve_acc.borrow().sunset_ts,
+        normal_mint_reserve_acc.borrow().grace_direction,
+        SwapDirection::NormalToPremium,
+        clock.unix_timestamp,
+    )?;
+
+    let mut supply_mode = premium_mint_reserve_acc.borrow().supply_mode;
+    let mut amount_in = amount;
+
+    if supply_mode == SupplyMode::MintAndBurn {
+        token_interface::burn(
+            CpiContext::new(
+                source.programs.get("normal_token_program")?,
+                token_interface::Burn {
+                    mint: normal_mint.to_account_info(),
+                    from: source.to_account_info(),
+                    authority: source_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    } else {
+        amount_in = pull_into_vault(
+            source.programs.get("normal_token_program")?,
+            source.to_account_info(),
+            source_authority.to_account_info(),
+            &normal_token_account,
+            &normal_mint,
+            amount,
+            &mut normal_mint_reserve_acc.borrow_mut().total_swapped_in,
+        )?;
     }
 
-    let mut premium_amount = premium_account.amount;
+    // The rate is expressed as normal tokens per premium token, so it is
+    // inverted when converting normal tokens into premium tokens.
+    let mut gross_amount_out = convert_between_mints(
+        amount_in,
+        normal_mint_reserve_acc.borrow().rate_denominator as u128,
+        normal_mint_reserve_acc.borrow().rate_numerator as u128,
+        normal_mint_reserve_acc.borrow().normal_decimals,
+        normal_mint_reserve_acc.borrow().premium_decimals,
+        normal_mint_reserve_acc.borrow().rounding,
+    )?;
+
+    // Fees are taken from the output and stay in the reserve's fee vault
+    // for the output mint until the creator claims them.
+    let fee_bps = normal_mint_reserve_acc
+        .borrow()
+        .fee_bps_override
+        .unwrap_or(premium_mint_reserve_acc.borrow().fee_bps);
+    let mut fee = swap_fee(gross_amount_out, fee_bps)?;
+    let mut amount_out = gross_amount_out
+        .checked_sub(fee)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
 
-    if !(premium_amount >= amount) {
-        panic!("Token amount too low to swap");
+    // Slippage is checked on what the destination receives. A Token-2022
+    // transfer fee on the premium mint is withheld from vault payouts, while
+    // minted output arrives whole.
+    let mut outbound_fee = 0;
+
+    if supply_mode == SupplyMode::Vault {
+        outbound_fee = transfer_fee(&premium_mint.to_account_info(), amount_out, clock.epoch)?;
     }
 
-    token::transfer(
-        CpiContext::new(
-            source.programs.get("token_program"),
-            token::Transfer {
-                from: source.to_account_info(),
-                authority: source_authority.to_account_info(),
-                to: normal_token_account.to_account_info(),
-            },
-        ),
-        amount,
-    )
-    .unwrap();
+    let net_amount_out = amount_out
+        .checked_sub(outbound_fee)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
 
-    token::transfer(
-        CpiContext::new(
-            premium_account.programs.get("token_program"),
-            token::Transfer {
-                from: premium_account.to_account_info(),
-                authority: premium_mint_reserve_acc
-                    .borrow()
-                    .__account__
-                    .to_account_info(),
-                to: destination.to_account_info(),
-            },
-        ),
-        amount,
-    )
-    .unwrap();
+    if !(net_amount_out >= min_amount_out) {
+        return Err(error!(TokenSwapError::SlippageExceeded));
+    }
+
+    let premium_mint_key = premium_mint_reserve_acc.borrow().premium_mint;
+    let random_hash = premium_mint_reserve_acc.borrow().random_hash.clone();
+    let bump = premium_mint_reserve_acc.borrow().bump;
+    let signer_seeds: &[&[u8]] = &[
+        "premium-reserve".as_bytes(),
+        premium_mint_key.as_ref(),
+        random_hash.as_bytes(),
+        &[bump],
+    ];
+
+    if supply_mode == SupplyMode::Vault {
+        let mut premium_amount = premium_account.amount;
+
+        if !(premium_amount >= gross_amount_out) {
+            return Err(error!(TokenSwapError::InsufficientLiquidity));
+        }
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                premium_account.programs.get("premium_token_program")?,
+                token_interface::TransferChecked {
+                    from: premium_account.to_account_info(),
+                    mint: premium_mint.to_account_info(),
+                    authority: premium_mint_reserve_acc
+                        .borrow()
+                        .__account__
+                        .to_account_info(),
+                    to: destination.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            amount_out,
+            premium_mint.decimals,
+        )?;
+
+        if fee > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    premium_account.programs.get("premium_token_program")?,
+                    token_interface::TransferChecked {
+                        from: premium_account.to_account_info(),
+                        mint: premium_mint.to_account_info(),
+                        authority: premium_mint_reserve_acc
+                            .borrow()
+                            .__account__
+                            .to_account_info(),
+                        to: fee_account.to_account_info(),
+                    },
+                    &[signer_seeds],
+                ),
+                fee,
+                premium_mint.decimals,
+            )?;
+        }
+
+        let total_swapped_out = premium_mint_reserve_acc
+            .borrow()
+            .total_swapped_out
+            .checked_add(gross_amount_out)
+            .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+        assign!(
+            premium_mint_reserve_acc.borrow_mut().total_swapped_out,
+            total_swapped_out
+        );
+    } else {
+        // Minted output never touches the vault, so its counters are left
+        // alone and the reserve still reconciles.
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                premium_account.programs.get("premium_token_program")?,
+                token_interface::MintTo {
+                    mint: premium_mint.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: premium_mint_reserve_acc
+                        .borrow()
+                        .__account__
+                        .to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            amount_out,
+        )?;
+
+        if fee > 0 {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    premium_account.programs.get("premium_token_program")?,
+                    token_interface::MintTo {
+                        mint: premium_mint.to_account_info(),
+                        to: fee_account.to_account_info(),
+                        authority: premium_mint_reserve_acc
+                            .borrow()
+                            .__account__
+                            .to_account_info(),
+                    },
+                    &[signer_seeds],
+                ),
+                fee,
+            )?;
+        }
+    }
+
+    let event = Swapped {
+        direction: SwapDirection::NormalToPremium,
+        user: source_authority.key(),
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        normal_mint_reserve_acc: normal_mint_reserve_acc.borrow().__account__.key(),
+        amount_in,
+        amount_out,
+        fee,
+    };
+
+    Ok(event)
 }
 
 pub fn withdraw_premium_tokens_handler<'info>(
     mut authority: SeahorseSigner<'info, '_>,
     mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
-    mut premium_account: SeahorseAccount<'info, '_, TokenAccount>,
-    mut destination: SeahorseAccount<'info, '_, TokenAccount>,
-    mut amount: u64,
-) -> () {
-    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
-        panic!("Invalid Authority");
+    mut premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut withdrawal_request: Mutable<LoadedWithdrawalRequest<'info, '_>>,
+    mut clock: Sysvar<'info, Clock>,
+) -> Result<Withdrawn> {
+    if !(premium_mint_reserve_acc.borrow().treasurer == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
     }
 
-    if !(premium_mint_reserve_acc.borrow().premium_account == premium_account.key()) {
-        panic!("Invalid Premium token account");
-    }
+    check_withdrawal_allowed(
+        &premium_mint_reserve_acc.borrow(),
+        &withdrawal_request.borrow(),
+        clock.unix_timestamp,
+    )?;
 
-    token::transfer(
-        CpiContext::new(
-            premium_account.programs.get("token_program"),
-            token::Transfer {
+    let mut amount = withdrawal_request.borrow().amount;
+
+    // The request is closed once it has been executed.
+    let open_withdrawal_requests = premium_mint_reserve_acc
+        .borrow()
+        .open_withdrawal_requests
+        .checked_sub(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc
+            .borrow_mut()
+            .open_withdrawal_requests,
+        open_withdrawal_requests
+    );
+
+    let premium_mint_key = premium_mint_reserve_acc.borrow().premium_mint;
+    let random_hash = premium_mint_reserve_acc.borrow().random_hash.clone();
+    let bump = premium_mint_reserve_acc.borrow().bump;
+    let signer_seeds: &[&[u8]] = &[
+        "premium-reserve".as_bytes(),
+        premium_mint_key.as_ref(),
+        random_hash.as_bytes(),
+        &[bump],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            premium_account.programs.get("token_program")?,
+            token_interface::TransferChecked {
                 from: premium_account.to_account_info(),
+                mint: premium_mint.to_account_info(),
                 authority: premium_mint_reserve_acc
                     .borrow()
                     .__account__
                     .to_account_info(),
                 to: destination.to_account_info(),
             },
+            &[signer_seeds],
         ),
         amount,
-    )
-    .unwrap();
+        premium_mint.decimals,
+    )?;
+
+    let total_withdrawn = premium_mint_reserve_acc
+        .borrow()
+        .total_withdrawn
+        .checked_add(amount)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().total_withdrawn,
+        total_withdrawn
+    );
+
+    let event = Withdrawn {
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        normal_mint_reserve_acc: None,
+        mint: premium_mint_reserve_acc.borrow().premium_mint,
+        destination: destination.key(),
+        amount,
+    };
+
+    Ok(event)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn swap_premium_tokens_for_normal_tokens_handler<'info>(
     mut source_authority: SeahorseSigner<'info, '_>,
     mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
     mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
-    mut normal_token_account: SeahorseAccount<'info, '_, TokenAccount>,
-    mut premium_account: SeahorseAccount<'info, '_, TokenAccount>,
-    mut source: SeahorseAccount<'info, '_, TokenAccount>,
-    mut destination: SeahorseAccount<'info, '_, TokenAccount>,
+    mut normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut source: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut fee_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut normal_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
     mut clock: Sysvar<'info, Clock>,
     mut amount: u64,
-) -> () {
-    if !(premium_mint_reserve_acc.borrow().premium_account == premium_account.key()) {
-        panic!("Invalid premium token account");
+    mut min_amount_out: u64,
+    mut deadline: Option<i64>,
+) -> Result<Swapped> {
+    if let Some(deadline) = deadline {
+        if !(clock.unix_timestamp <= deadline) {
+            return Err(error!(TokenSwapError::DeadlineExceeded));
+        }
     }
 
-    if !(normal_mint_reserve_acc.borrow().premium_mint_reserve_acc
-        == premium_mint_reserve_acc.borrow().__account__.key())
+    if premium_mint_reserve_acc.borrow().premium_to_normal_paused
+        || normal_mint_reserve_acc.borrow().premium_to_normal_paused
     {
-        panic!("The premium and the normal reserves are not related");
-    }
-
-    if !(normal_mint_reserve_acc.borrow().normal_token_account == normal_token_account.key()) {
-        panic!("Invalid normal token account");
+        return Err(error!(TokenSwapError::SwapsPaused));
     }
 
     if !(premium_mint_reserve_acc.borrow().go_live_timestamp < clock.unix_timestamp) {
-        panic!("Premium reserve not live yet");
+        return Err(error!(TokenSwapError::PremiumReserveNotLive));
     }
 
     if !(normal_mint_reserve_acc.borrow().go_live_ts < clock.unix_timestamp) {
-        panic!("Normal reserve not live yet");
+        return Err(error!(TokenSwapError::NormalReserveNotLive));
+    }
+
+    check_swap_window(
+        premium_mint_reserve_acc.borrow().end_ts,
+        premium_mint_reserve_acc.borrow().sunset_ts,
+        premium_mint_reserve_acc.borrow().grace_direction,
+        SwapDirection::PremiumToNormal,
+        clock.unix_timestamp,
+    )?;
+
+    check_swap_window(
+        normal_mint_reserve_acc.borrow().end_ts,
+        normal_mint_reserve_acc.borrow().sunset_ts,
+        normal_mint_reserve_acc.borrow().grace_direction,
+        SwapDirection::PremiumToNormal,
+        clock.unix_timestamp,
+    )?;
+
+    let mut amount_in = amount;
+
+    if premium_mint_reserve_acc.borrow().supply_mode == SupplyMode::Vault {
+        amount_in = pull_into_vault(
+            source.programs.get("premium_token_program")?,
+            source.to_account_info(),
+            source_authority.to_account_info(),
+            &premium_account,
+            &premium_mint,
+            amount,
+            &mut premium_mint_reserve_acc.borrow_mut().total_swapped_in,
+        )?;
+    } else {
+        token_interface::burn(
+            CpiContext::new(
+                source.programs.get("premium_token_program")?,
+                token_interface::Burn {
+                    mint: premium_mint.to_account_info(),
+                    from: source.to_account_info(),
+                    authority: source_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    let mut gross_amount_out = convert_between_mints(
+        amount_in,
+        normal_mint_reserve_acc.borrow().rate_numerator as u128,
+        normal_mint_reserve_acc.borrow().rate_denominator as u128,
+        normal_mint_reserve_acc.borrow().premium_decimals,
+        normal_mint_reserve_acc.borrow().normal_decimals,
+        normal_mint_reserve_acc.borrow().rounding,
+    )?;
+
+    // Fees are taken from the output and stay in the reserve's fee vault
+    // for the output mint until the creator claims them.
+    let fee_bps = normal_mint_reserve_acc
+        .borrow()
+        .fee_bps_override
+        .unwrap_or(premium_mint_reserve_acc.borrow().fee_bps);
+    let mut fee = swap_fee(gross_amount_out, fee_bps)?;
+    let mut amount_out = gross_amount_out
+        .checked_sub(fee)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    // Slippage is checked on what the destination receives, after any
+    // Token-2022 transfer fee the output mint withholds.
+    let outbound_fee = transfer_fee(&normal_mint.to_account_info(), amount_out, clock.epoch)?;
+    let net_amount_out = amount_out
+        .checked_sub(outbound_fee)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    if !(net_amount_out >= min_amount_out) {
+        return Err(error!(TokenSwapError::SlippageExceeded));
     }
 
     let mut normal_amount = normal_token_account.amount;
 
-    if !(normal_amount >= amount) {
-        panic!("Token amount too low to swap");
+    if !(normal_amount >= gross_amount_out) {
+        return Err(error!(TokenSwapError::InsufficientLiquidity));
     }
 
-    token::transfer(
-        CpiContext::new(
-            source.programs.get("token_program"),
-            token::Transfer {
-                from: source.to_account_info(),
-                authority: source_authority.to_account_info(),
-                to: premium_account.to_account_info(),
-            },
-        ),
-        amount,
-    )
-    .unwrap();
+    let premium_mint_reserve_key = normal_mint_reserve_acc.borrow().premium_mint_reserve_acc;
+    let normal_mint_key = normal_mint_reserve_acc.borrow().normal_mint;
+    let bump = normal_mint_reserve_acc.borrow().bump;
+    let signer_seeds: &[&[u8]] = &[
+        "normal-mint-reserve".as_bytes(),
+        premium_mint_reserve_key.as_ref(),
+        normal_mint_key.as_ref(),
+        &[bump],
+    ];
 
-    token::transfer(
-        CpiContext::new(
-            normal_token_account.programs.get("token_program"),
-            token::Transfer {
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            normal_token_account.programs.get("normal_token_program")?,
+            token_interface::TransferChecked {
                 from: normal_token_account.to_account_info(),
+                mint: normal_mint.to_account_info(),
                 authority: normal_mint_reserve_acc
                     .borrow()
                     .__account__
                     .to_account_info(),
                 to: destination.to_account_info(),
             },
+            &[signer_seeds],
         ),
-        amount,
-    )
-    .unwrap();
-}
-
-pub fn create_normal_mint_reserve_handler<'info>(
-    mut payer: SeahorseSigner<'info, '_>,
-    mut normal_token_account: Empty<SeahorseAccount<'info, '_, TokenAccount>>,
-    mut normal_mint_reserve_acc: Empty<Mutable<LoadedNormalMintReserve<'info, '_>>>,
-    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
-    mut normal_mint: SeahorseAccount<'info, '_, Mint>,
-    mut go_live_ts: i64,
-    mut initialization_ts: i64,
-    mut clock: Sysvar<'info, Clock>,
-) -> () {
-    let mut bump = normal_mint_reserve_acc.bump.unwrap();
-    let mut normal_mint_reserve_acc = normal_mint_reserve_acc.account.clone();
-    let mut token_bump = normal_token_account.bump.unwrap();
-    let mut normal_token_account = normal_token_account.account.clone();
-
-    if !(premium_mint_reserve_acc.borrow().premium_mint != normal_mint.key()) {
-        panic!("premium mint can't be the same as normal mint");
-    }
+        amount_out,
+        normal_mint.decimals,
+    )?;
 
-    if !(premium_mint_reserve_acc.borrow().creator == payer.key()) {
-        panic!("creators do not match");
+    if fee > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                normal_token_account.programs.get("normal_token_program")?,
+                token_interface::TransferChecked {
+                    from: normal_token_account.to_account_info(),
+                    mint: normal_mint.to_account_info(),
+                    authority: normal_mint_reserve_acc
+                        .borrow()
+                        .__account__
+                        .to_account_info(),
+                    to: fee_account.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            fee,
+            normal_mint.decimals,
+        )?;
     }
 
-    assign!(
-        normal_mint_reserve_acc
-            .borrow_mut()
-            .premium_mint_reserve_acc,
-        premium_mint_reserve_acc.borrow().__account__.key()
-    );
+    let total_swapped_out = normal_mint_reserve_acc
+        .borrow()
+        .total_swapped_out
+        .checked_add(gross_amount_out)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
 
     assign!(
-        normal_mint_reserve_acc.borrow_mut().normal_mint,
-        normal_mint.key()
+        normal_mint_reserve_acc.borrow_mut().total_swapped_out,
+        total_swapped_out
     );
 
-    assign!(
-        normal_mint_reserve_acc.borrow_mut().normal_token_account,
-        normal_token_account.key()
-    );
+    let event = Swapped {
+        direction: SwapDirection::PremiumToNormal,
+        user: source_authority.key(),
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        normal_mint_reserve_acc: normal_mint_reserve_acc.borrow().__account__.key(),
+        amount_in,
+        amount_out,
+        fee,
+    };
 
-    assign!(
-        normal_mint_reserve_acc.borrow_mut().initialization_ts,
-        initialization_ts
-    );
+    Ok(event)
+}
 
-    assign!(normal_mint_reserve_acc.borrow_mut().bump, bump);
+// SPL Token and Token-2022 each have their own wrapped-SOL mint; a reserve
+// over either one can trade native SOL.
+pub fn is_native_mint(mint: Pubkey) -> bool {
+    mint == anchor_spl::token::spl_token::native_mint::ID || mint == spl_token_2022::native_mint::ID
+}
 
-    assign!(normal_mint_reserve_acc.borrow_mut().token_bump, token_bump);
+// Native SOL enters a wrapped-SOL normal reserve through a temporary token
+// account owned by the user: the lamports are wrapped into it, swapped from
+// it like any other source, and the emptied account is closed again.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_sol_for_premium_tokens_handler<'info>(
+    mut source_authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut wrapped_sol_account: Empty<SeahorseInterfaceAccount<'info, '_, TokenAccount>>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut fee_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut normal_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut clock: Sysvar<'info, Clock>,
+    mut amount: u64,
+    mut min_amount_out: u64,
+    mut deadline: Option<i64>,
+) -> Result<Swapped> {
+    let mut wrapped_sol_account = wrapped_sol_account.account.clone();
 
-    if go_live_ts < clock.unix_timestamp {
-        assign!(
-            normal_mint_reserve_acc.borrow_mut().go_live_ts,
-            clock.unix_timestamp
-        );
-    } else {
-        assign!(normal_mint_reserve_acc.borrow_mut().go_live_ts, go_live_ts);
+    if !is_native_mint(normal_mint_reserve_acc.borrow().normal_mint) {
+        return Err(error!(TokenSwapError::NotNativeMint));
     }
-}
 
-pub fn create_premium_mint_reserve_handler<'info>(
-    mut payer: SeahorseSigner<'info, '_>,
+    system_program::transfer(
+        CpiContext::new(
+            source_authority.programs.get("system_program")?,
+            system_program::Transfer {
+                from: source_authority.to_account_info(),
+                to: wrapped_sol_account.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    token_interface::sync_native(CpiContext::new(
+        wrapped_sol_account.programs.get("normal_token_program")?,
+        token_interface::SyncNative {
+            account: wrapped_sol_account.to_account_info(),
+        },
+    ))?;
+
+    let swapped = swap_normal_tokens_for_premium_tokens_handler(
+        source_authority.clone(),
+        premium_mint_reserve_acc.clone(),
+        normal_mint_reserve_acc.clone(),
+        normal_token_account.clone(),
+        premium_account.clone(),
+        wrapped_sol_account.clone(),
+        destination.clone(),
+        fee_account.clone(),
+        premium_mint.clone(),
+        normal_mint.clone(),
+        clock.clone(),
+        amount,
+        min_amount_out,
+        deadline,
+    )?;
+
+    token_interface::close_account(CpiContext::new(
+        wrapped_sol_account.programs.get("normal_token_program")?,
+        token_interface::CloseAccount {
+            account: wrapped_sol_account.to_account_info(),
+            destination: source_authority.to_account_info(),
+            authority: source_authority.to_account_info(),
+        },
+    ))?;
+
+    Ok(swapped)
+}
+
+// The reverse trip pays the wrapped SOL out into a temporary account and
+// closes it, which unwraps the balance into the user's lamports.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_premium_tokens_for_sol_handler<'info>(
+    mut source_authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut source: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut wrapped_sol_account: Empty<SeahorseInterfaceAccount<'info, '_, TokenAccount>>,
+    mut fee_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut normal_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut clock: Sysvar<'info, Clock>,
+    mut amount: u64,
+    mut min_amount_out: u64,
+    mut deadline: Option<i64>,
+) -> Result<Swapped> {
+    let mut wrapped_sol_account = wrapped_sol_account.account.clone();
+
+    if !is_native_mint(normal_mint_reserve_acc.borrow().normal_mint) {
+        return Err(error!(TokenSwapError::NotNativeMint));
+    }
+
+    let swapped = swap_premium_tokens_for_normal_tokens_handler(
+        source_authority.clone(),
+        premium_mint_reserve_acc.clone(),
+        normal_mint_reserve_acc.clone(),
+        normal_token_account.clone(),
+        premium_account.clone(),
+        source.clone(),
+        wrapped_sol_account.clone(),
+        fee_account.clone(),
+        premium_mint.clone(),
+        normal_mint.clone(),
+        clock.clone(),
+        amount,
+        min_amount_out,
+        deadline,
+    )?;
+
+    token_interface::close_account(CpiContext::new(
+        wrapped_sol_account.programs.get("normal_token_program")?,
+        token_interface::CloseAccount {
+            account: wrapped_sol_account.to_account_info(),
+            destination: source_authority.to_account_info(),
+            authority: source_authority.to_account_info(),
+        },
+    ))?;
+
+    Ok(swapped)
+}
+
+// Swaps one normal mint for another linked to the same premium reserve in a
+// single step. Both rates are applied through the premium mint, but no
+// premium tokens move: the input lands in the first normal vault and the
+// output leaves the second, with one fee taken on the way out.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_normal_for_normal_handler<'info>(
+    mut source_authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut from_normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut to_normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut from_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut to_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut source: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut fee_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut from_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut to_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut clock: Sysvar<'info, Clock>,
+    mut amount: u64,
+    mut min_amount_out: u64,
+    mut deadline: Option<i64>,
+) -> Result<NormalSwapped> {
+    if let Some(deadline) = deadline {
+        if !(clock.unix_timestamp <= deadline) {
+            return Err(error!(TokenSwapError::DeadlineExceeded));
+        }
+    }
+
+    if !(from_normal_mint_reserve_acc.borrow().normal_mint
+        != to_normal_mint_reserve_acc.borrow().normal_mint)
+    {
+        return Err(error!(TokenSwapError::SameMint));
+    }
+
+    // The trip is a normal-to-premium swap on the first reserve followed by
+    // a premium-to-normal swap on the second, so both legs must be open.
+    if premium_mint_reserve_acc.borrow().normal_to_premium_paused
+        || premium_mint_reserve_acc.borrow().premium_to_normal_paused
+        || from_normal_mint_reserve_acc
+            .borrow()
+            .normal_to_premium_paused
+        || to_normal_mint_reserve_acc.borrow().premium_to_normal_paused
+    {
+        return Err(error!(TokenSwapError::SwapsPaused));
+    }
+
+    if !(premium_mint_reserve_acc.borrow().go_live_timestamp < clock.unix_timestamp) {
+        return Err(error!(TokenSwapError::PremiumReserveNotLive));
+    }
+
+    if !(from_normal_mint_reserve_acc.borrow().go_live_ts < clock.unix_timestamp
+        && to_normal_mint_reserve_acc.borrow().go_live_ts < clock.unix_timestamp)
+    {
+        return Err(error!(TokenSwapError::NormalReserveNotLive));
+    }
+
+    // No premium changes hands, so the premium reserve's window is checked
+    // once, for the premium-to-normal leg that pays the output.
+    check_swap_window(
+        premium_mint_reserve_acc.borrow().end_ts,
+        premium_mint_reserve_acc.borrow().sunset_ts,
+        premium_mint_reserve_acc.borrow().grace_direction,
+        SwapDirection::PremiumToNormal,
+        clock.unix_timestamp,
+    )?;
+
+    check_swap_window(
+        from_normal_mint_reserve_acc.borrow().end_ts,
+        from_normal_mint_reserve_acc.borrow().sunset_ts,
+        from_normal_mint_reserve_acc.borrow().grace_direction,
+        SwapDirection::NormalToPremium,
+        clock.unix_timestamp,
+    )?;
+
+    check_swap_window(
+        to_normal_mint_reserve_acc.borrow().end_ts,
+        to_normal_mint_reserve_acc.borrow().sunset_ts,
+        to_normal_mint_reserve_acc.borrow().grace_direction,
+        SwapDirection::PremiumToNormal,
+        clock.unix_timestamp,
+    )?;
+
+    let mut amount_in = amount;
+
+    if premium_mint_reserve_acc.borrow().supply_mode == SupplyMode::MintAndBurn {
+        token_interface::burn(
+            CpiContext::new(
+                source.programs.get("from_token_program")?,
+                token_interface::Burn {
+                    mint: from_mint.to_account_info(),
+                    from: source.to_account_info(),
+                    authority: source_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    } else {
+        amount_in = pull_into_vault(
+            source.programs.get("from_token_program")?,
+            source.to_account_info(),
+            source_authority.to_account_info(),
+            &from_token_account,
+            &from_mint,
+            amount,
+            &mut from_normal_mint_reserve_acc.borrow_mut().total_swapped_in,
+        )?;
+    }
+
+    // Both rates go through the premium mint, so they compose into a single
+    // from-to-output rate and the input is rounded once, with the output
+    // reserve's policy.
+    let (numerator, denominator) = normal_to_normal_rate(
+        from_normal_mint_reserve_acc.borrow().rate_numerator,
+        from_normal_mint_reserve_acc.borrow().rate_denominator,
+        to_normal_mint_reserve_acc.borrow().rate_numerator,
+        to_normal_mint_reserve_acc.borrow().rate_denominator,
+    );
+    let mut gross_amount_out = convert_between_mints(
+        amount_in,
+        numerator,
+        denominator,
+        from_normal_mint_reserve_acc.borrow().normal_decimals,
+        to_normal_mint_reserve_acc.borrow().normal_decimals,
+        to_normal_mint_reserve_acc.borrow().rounding,
+    )?;
+
+    let fee_bps = to_normal_mint_reserve_acc
+        .borrow()
+        .fee_bps_override
+        .unwrap_or(premium_mint_reserve_acc.borrow().fee_bps);
+    let mut fee = swap_fee(gross_amount_out, fee_bps)?;
+    let mut amount_out = gross_amount_out
+        .checked_sub(fee)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    // Slippage is checked on what the destination receives, after any
+    // Token-2022 transfer fee the output mint withholds.
+    let outbound_fee = transfer_fee(&to_mint.to_account_info(), amount_out, clock.epoch)?;
+    let net_amount_out = amount_out
+        .checked_sub(outbound_fee)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    if !(net_amount_out >= min_amount_out) {
+        return Err(error!(TokenSwapError::SlippageExceeded));
+    }
+
+    let mut to_amount = to_token_account.amount;
+
+    if !(to_amount >= gross_amount_out) {
+        return Err(error!(TokenSwapError::InsufficientLiquidity));
+    }
+
+    let premium_mint_reserve_key = premium_mint_reserve_acc.borrow().__account__.key();
+    let to_mint_key = to_normal_mint_reserve_acc.borrow().normal_mint;
+    let bump = to_normal_mint_reserve_acc.borrow().bump;
+    let signer_seeds: &[&[u8]] = &[
+        "normal-mint-reserve".as_bytes(),
+        premium_mint_reserve_key.as_ref(),
+        to_mint_key.as_ref(),
+        &[bump],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            to_token_account.programs.get("to_token_program")?,
+            token_interface::TransferChecked {
+                from: to_token_account.to_account_info(),
+                mint: to_mint.to_account_info(),
+                authority: to_normal_mint_reserve_acc
+                    .borrow()
+                    .__account__
+                    .to_account_info(),
+                to: destination.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        amount_out,
+        to_mint.decimals,
+    )?;
+
+    if fee > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                to_token_account.programs.get("to_token_program")?,
+                token_interface::TransferChecked {
+                    from: to_token_account.to_account_info(),
+                    mint: to_mint.to_account_info(),
+                    authority: to_normal_mint_reserve_acc
+                        .borrow()
+                        .__account__
+                        .to_account_info(),
+                    to: fee_account.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            fee,
+            to_mint.decimals,
+        )?;
+    }
+
+    let total_swapped_out = to_normal_mint_reserve_acc
+        .borrow()
+        .total_swapped_out
+        .checked_add(gross_amount_out)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        to_normal_mint_reserve_acc.borrow_mut().total_swapped_out,
+        total_swapped_out
+    );
+
+    let event = NormalSwapped {
+        user: source_authority.key(),
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        from_normal_mint_reserve_acc: from_normal_mint_reserve_acc.borrow().__account__.key(),
+        to_normal_mint_reserve_acc: to_normal_mint_reserve_acc.borrow().__account__.key(),
+        amount_in,
+        amount_out,
+        fee,
+    };
+
+    Ok(event)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_normal_mint_reserve_handler<'info>(
+    mut payer: SeahorseSigner<'info, '_>,
+    mut normal_token_account: Empty<SeahorseInterfaceAccount<'info, '_, TokenAccount>>,
+    mut normal_mint_reserve_acc: Empty<Mutable<LoadedNormalMintReserve<'info, '_>>>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut normal_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut go_live_ts: i64,
+    mut clock: Sysvar<'info, Clock>,
+    mut rate_numerator: u64,
+    mut rate_denominator: u64,
+    mut rounding: RoundingPolicy,
+    mut fee_bps_override: Option<u16>,
+    mut end_ts: Option<i64>,
+    mut sunset_ts: Option<i64>,
+    mut grace_direction: SwapDirection,
+) -> Result<NormalReserveLinked> {
+    let mut bump = normal_mint_reserve_acc
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
+    let mut normal_mint_reserve_acc = normal_mint_reserve_acc.account.clone();
+    let mut token_bump = normal_token_account
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
+    let mut normal_token_account = normal_token_account.account.clone();
+
+    if !(premium_mint_reserve_acc.borrow().premium_mint != normal_mint.key()) {
+        return Err(error!(TokenSwapError::SameMint));
+    }
+
+    check_mint_extensions(&normal_mint.to_account_info())?;
+
+    if !(!is_native_mint(normal_mint.key())
+        || premium_mint_reserve_acc.borrow().supply_mode != SupplyMode::MintAndBurn)
+    {
+        return Err(error!(TokenSwapError::NativeMintNotBurnable));
+    }
+
+    if !(premium_mint_reserve_acc.borrow().creator == payer.key()) {
+        return Err(error!(TokenSwapError::CreatorMismatch));
+    }
+
+    if !(rate_numerator > 0 && rate_denominator > 0) {
+        return Err(error!(TokenSwapError::InvalidRate));
+    }
+
+    if let Some(fee_bps) = fee_bps_override {
+        if !(fee_bps <= 10_000) {
+            return Err(error!(TokenSwapError::InvalidFee));
+        }
+    }
+
+    if !(premium_mint_reserve_acc.borrow().linked_normal_mints
+        < premium_mint_reserve_acc.borrow().normal_mints)
+    {
+        return Err(error!(TokenSwapError::NormalMintCapReached));
+    }
+
+    assign!(
+        normal_mint_reserve_acc
+            .borrow_mut()
+            .premium_mint_reserve_acc,
+        premium_mint_reserve_acc.borrow().__account__.key()
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().normal_mint,
+        normal_mint.key()
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().normal_token_account,
+        normal_token_account.key()
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().initialization_ts,
+        clock.unix_timestamp
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().initialization_slot,
+        clock.slot
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().normal_token_program,
+        *normal_token_account.to_account_info().owner
+    );
+
+    assign!(normal_mint_reserve_acc.borrow_mut().bump, bump);
+
+    assign!(normal_mint_reserve_acc.borrow_mut().token_bump, token_bump);
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().rate_numerator,
+        rate_numerator
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().rate_denominator,
+        rate_denominator
+    );
+
+    assign!(normal_mint_reserve_acc.borrow_mut().rounding, rounding);
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().premium_decimals,
+        premium_mint.decimals
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().normal_decimals,
+        normal_mint.decimals
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().fee_bps_override,
+        fee_bps_override
+    );
+
+    let linked_normal_mints = premium_mint_reserve_acc
+        .borrow()
+        .linked_normal_mints
+        .checked_add(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().linked_normal_mints,
+        linked_normal_mints
+    );
+
+    if go_live_ts < clock.unix_timestamp {
+        assign!(
+            normal_mint_reserve_acc.borrow_mut().go_live_ts,
+            clock.unix_timestamp
+        );
+    } else {
+        assign!(normal_mint_reserve_acc.borrow_mut().go_live_ts, go_live_ts);
+    }
+
+    check_schedule(
+        normal_mint_reserve_acc.borrow().go_live_ts,
+        end_ts,
+        sunset_ts,
+    )?;
+
+    assign!(normal_mint_reserve_acc.borrow_mut().end_ts, end_ts);
+
+    assign!(normal_mint_reserve_acc.borrow_mut().sunset_ts, sunset_ts);
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().grace_direction,
+        grace_direction
+    );
+
+    let event = NormalReserveLinked {
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        normal_mint_reserve_acc: normal_mint_reserve_acc.borrow().__account__.key(),
+        normal_mint: normal_mint.key(),
+        normal_token_account: normal_token_account.key(),
+        go_live_ts: normal_mint_reserve_acc.borrow().go_live_ts,
+        rate_numerator,
+        rate_denominator,
+    };
+
+    Ok(event)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_premium_mint_reserve_handler<'info>(
+    mut payer: SeahorseSigner<'info, '_>,
     mut premium_mint_reserve_acc: Empty<Mutable<LoadedPremiumMintReserve<'info, '_>>>,
-    mut premium_mint: SeahorseAccount<'info, '_, Mint>,
-    mut premium_account: Empty<SeahorseAccount<'info, '_, TokenAccount>>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut premium_account: Empty<SeahorseInterfaceAccount<'info, '_, TokenAccount>>,
     mut go_live_timestamp: i64,
     mut clock: Sysvar<'info, Clock>,
     mut normal_mints: u32,
     mut random_hash: String,
-) -> () {
-    let mut bump = premium_mint_reserve_acc.bump.unwrap();
+    mut fee_bps: u16,
+    mut end_ts: Option<i64>,
+    mut sunset_ts: Option<i64>,
+    mut grace_direction: SwapDirection,
+    mut supply_mode: SupplyMode,
+    mut withdrawal_delay: i64,
+) -> Result<PremiumReserveCreated> {
+    let mut bump = premium_mint_reserve_acc
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
     let mut premium_mint_reserve_acc = premium_mint_reserve_acc.account.clone();
-    let mut token_bump = premium_account.bump.unwrap();
+    let mut token_bump = premium_account
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
     let mut premium_account = premium_account.account.clone();
 
+    if !(fee_bps <= 10_000) {
+        return Err(error!(TokenSwapError::InvalidFee));
+    }
+
+    check_withdrawal_delay(withdrawal_delay)?;
+
+    check_mint_extensions(&premium_mint.to_account_info())?;
+
+    // The creator hands the mint authority to the reserve's PDA beforehand;
+    // swaps would otherwise fail only once someone tried to mint.
+    let mut reserve_is_mint_authority = premium_mint.mint_authority
+        == solana_program::program_option::COption::Some(
+            premium_mint_reserve_acc.borrow().__account__.key(),
+        );
+
+    if !(supply_mode == SupplyMode::Vault || reserve_is_mint_authority) {
+        return Err(error!(TokenSwapError::MintAuthorityNotReserve));
+    }
+
     assign!(
         premium_mint_reserve_acc.borrow_mut().premium_mint,
         premium_mint.key()
@@ -488,6 +2601,16 @@ pub fn create_premium_mint_reserve_handler<'info>(
         clock.unix_timestamp
     );
 
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().initialization_slot,
+        clock.slot
+    );
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().premium_token_program,
+        *premium_account.to_account_info().owner
+    );
+
     assign!(
         premium_mint_reserve_acc.borrow_mut().random_hash,
         random_hash
@@ -495,6 +2618,11 @@ pub fn create_premium_mint_reserve_handler<'info>(
 
     assign!(premium_mint_reserve_acc.borrow_mut().creator, payer.key());
 
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().pause_authority,
+        payer.key()
+    );
+
     assign!(premium_mint_reserve_acc.borrow_mut().bump, bump);
 
     assign!(premium_mint_reserve_acc.borrow_mut().token_bump, token_bump);
@@ -504,6 +2632,24 @@ pub fn create_premium_mint_reserve_handler<'info>(
         normal_mints
     );
 
+    assign!(premium_mint_reserve_acc.borrow_mut().linked_normal_mints, 0);
+
+    assign!(premium_mint_reserve_acc.borrow_mut().fee_bps, fee_bps);
+
+    assign!(premium_mint_reserve_acc.borrow_mut().pending_creator, None);
+
+    assign!(premium_mint_reserve_acc.borrow_mut().treasurer, payer.key());
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().withdrawal_delay,
+        withdrawal_delay
+    );
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().liquidity_locked_until,
+        0
+    );
+
     if go_live_timestamp < clock.unix_timestamp {
         assign!(
             premium_mint_reserve_acc.borrow_mut().go_live_timestamp,
@@ -515,4 +2661,1726 @@ pub fn create_premium_mint_reserve_handler<'info>(
             go_live_timestamp
         );
     }
+
+    check_schedule(
+        premium_mint_reserve_acc.borrow().go_live_timestamp,
+        end_ts,
+        sunset_ts,
+    )?;
+
+    assign!(premium_mint_reserve_acc.borrow_mut().end_ts, end_ts);
+
+    assign!(premium_mint_reserve_acc.borrow_mut().sunset_ts, sunset_ts);
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().grace_direction,
+        grace_direction
+    );
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().supply_mode,
+        supply_mode
+    );
+
+    let event = PremiumReserveCreated {
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        premium_mint: premium_mint.key(),
+        premium_account: premium_account.key(),
+        creator: payer.key(),
+        go_live_timestamp: premium_mint_reserve_acc.borrow().go_live_timestamp,
+        normal_mints,
+        fee_bps,
+        supply_mode,
+    };
+
+    Ok(event)
+}
+
+pub fn migrate_premium_account_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: AccountInfo<'info>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut legacy_premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_account: Empty<SeahorseInterfaceAccount<'info, '_, TokenAccount>>,
+    mut withdrawal_delay: i64,
+) -> Result<()> {
+    let mut token_bump = premium_account
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
+    let mut premium_account = premium_account.account.clone();
+    let mut legacy = LegacyPremiumMintReserve::load(&premium_mint_reserve_acc)?;
+
+    if !(legacy.creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    if !(legacy.premium_mint == premium_mint.key()) {
+        return Err(error!(TokenSwapError::InvalidPremiumMint));
+    }
+
+    if !(legacy.premium_account == legacy_premium_account.key()) {
+        return Err(error!(TokenSwapError::InvalidPremiumTokenAccount));
+    }
+
+    check_withdrawal_delay(withdrawal_delay)?;
+
+    // The vault is moved under the per-reserve seeds, so the legacy balance is
+    // swept into the new account before the old one is closed.
+    let signer_seeds: &[&[u8]] = &[
+        "premium-reserve".as_bytes(),
+        legacy.premium_mint.as_ref(),
+        legacy.random_hash.as_bytes(),
+        &[legacy.bump],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            legacy_premium_account.programs.get("token_program")?,
+            token_interface::TransferChecked {
+                from: legacy_premium_account.to_account_info(),
+                mint: premium_mint.to_account_info(),
+                authority: premium_mint_reserve_acc.clone(),
+                to: premium_account.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        legacy_premium_account.amount,
+        premium_mint.decimals,
+    )?;
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        legacy_premium_account.programs.get("token_program")?,
+        token_interface::CloseAccount {
+            account: legacy_premium_account.to_account_info(),
+            destination: authority.to_account_info(),
+            authority: premium_mint_reserve_acc.clone(),
+        },
+        &[signer_seeds],
+    ))?;
+
+    let mut upgraded = legacy.upgrade();
+
+    upgraded.premium_account = premium_account.key();
+    upgraded.token_bump = token_bump;
+    upgraded.withdrawal_delay = withdrawal_delay;
+    // Whatever was funded before counters existed is treated as deposited.
+    upgraded.total_deposited = legacy_premium_account.amount;
+
+    // Grow the account to the current layout, topping up rent from the
+    // authority, then rewrite it in place.
+    let space = PremiumMintReserve::SPACE;
+
+    if premium_mint_reserve_acc.data_len() < space {
+        let rent_due = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(premium_mint_reserve_acc.lamports());
+
+        if rent_due > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    authority.programs.get("system_program")?,
+                    system_program::Transfer {
+                        from: authority.to_account_info(),
+                        to: premium_mint_reserve_acc.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+
+        premium_mint_reserve_acc.realloc(space, false)?;
+    }
+
+    let mut data = premium_mint_reserve_acc.try_borrow_mut_data()?;
+
+    upgraded.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn migrate_normal_mint_reserve_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut legacy_normal_mint_reserve_acc: AccountInfo<'info>,
+    mut legacy_normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut normal_mint_reserve_acc: Empty<Mutable<LoadedNormalMintReserve<'info, '_>>>,
+    mut normal_token_account: Empty<SeahorseInterfaceAccount<'info, '_, TokenAccount>>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut normal_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+) -> Result<()> {
+    let mut bump = normal_mint_reserve_acc
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
+    let mut normal_mint_reserve_acc = normal_mint_reserve_acc.account.clone();
+    let mut token_bump = normal_token_account
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
+    let mut normal_token_account = normal_token_account.account.clone();
+    let mut legacy = LegacyNormalMintReserve::load(&legacy_normal_mint_reserve_acc)?;
+
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    if !(legacy.premium_mint_reserve_acc == premium_mint_reserve_acc.borrow().__account__.key()) {
+        return Err(error!(TokenSwapError::ReservesNotRelated));
+    }
+
+    if !(legacy.normal_mint == normal_mint.key()) {
+        return Err(error!(TokenSwapError::InvalidNormalMint));
+    }
+
+    if !(legacy.normal_token_account == legacy_normal_token_account.key()) {
+        return Err(error!(TokenSwapError::InvalidNormalTokenAccount));
+    }
+
+    if !(premium_mint_reserve_acc.borrow().linked_normal_mints
+        < premium_mint_reserve_acc.borrow().normal_mints)
+    {
+        return Err(error!(TokenSwapError::NormalMintCapReached));
+    }
+
+    assign!(
+        normal_mint_reserve_acc
+            .borrow_mut()
+            .premium_mint_reserve_acc,
+        premium_mint_reserve_acc.borrow().__account__.key()
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().normal_mint,
+        normal_mint.key()
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().normal_token_account,
+        normal_token_account.key()
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().go_live_ts,
+        legacy.go_live_ts
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().initialization_ts,
+        legacy.initialization_ts
+    );
+
+    assign!(normal_mint_reserve_acc.borrow_mut().bump, bump);
+
+    assign!(normal_mint_reserve_acc.borrow_mut().token_bump, token_bump);
+
+    let linked_normal_mints = premium_mint_reserve_acc
+        .borrow()
+        .linked_normal_mints
+        .checked_add(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().linked_normal_mints,
+        linked_normal_mints
+    );
+
+    // Whatever was funded before counters existed is treated as deposited.
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().total_deposited,
+        legacy_normal_token_account.amount
+    );
+
+    // Legacy reserves always swapped raw amounts 1:1, which in UI units
+    // depends on the two mints' decimals.
+    let (rate_numerator, rate_denominator) =
+        raw_parity_rate(premium_mint.decimals, normal_mint.decimals)?;
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().rate_numerator,
+        rate_numerator
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().rate_denominator,
+        rate_denominator
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().premium_decimals,
+        premium_mint.decimals
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().normal_decimals,
+        normal_mint.decimals
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().normal_token_program,
+        *normal_token_account.to_account_info().owner
+    );
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().rounding,
+        RoundingPolicy::Down
+    );
+
+    // The legacy reserve PDA was derived from the constant seed alone.
+    let signer_seeds: &[&[u8]] = &["normal-mint-reserve".as_bytes(), &[legacy.bump]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            legacy_normal_token_account.programs.get("token_program")?,
+            token_interface::TransferChecked {
+                from: legacy_normal_token_account.to_account_info(),
+                mint: normal_mint.to_account_info(),
+                authority: legacy_normal_mint_reserve_acc.clone(),
+                to: normal_token_account.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        legacy_normal_token_account.amount,
+        normal_mint.decimals,
+    )?;
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        legacy_normal_token_account.programs.get("token_program")?,
+        token_interface::CloseAccount {
+            account: legacy_normal_token_account.to_account_info(),
+            destination: authority.to_account_info(),
+            authority: legacy_normal_mint_reserve_acc.clone(),
+        },
+        &[signer_seeds],
+    ))?;
+
+    // The legacy layout can't be loaded as an `Account`, so it is closed by
+    // hand: drain the rent to the authority and wipe the data.
+    let authority_info = authority.to_account_info();
+    let legacy_lamports = legacy_normal_mint_reserve_acc.lamports();
+
+    **authority_info.lamports.borrow_mut() = authority_info
+        .lamports()
+        .checked_add(legacy_lamports)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    **legacy_normal_mint_reserve_acc.lamports.borrow_mut() = 0;
+
+    legacy_normal_mint_reserve_acc
+        .try_borrow_mut_data()?
+        .fill(0);
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn close_normal_mint_reserve_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut normal_fee_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut normal_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut clock: Sysvar<'info, Clock>,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    // Closing pays the vaults out at once, so like a withdrawal it waits for
+    // any liquidity lock to expire.
+    if !(premium_mint_reserve_acc.borrow().liquidity_locked_until <= clock.unix_timestamp) {
+        return Err(error!(TokenSwapError::LiquidityLocked));
+    }
+
+    let premium_mint_reserve_key = normal_mint_reserve_acc.borrow().premium_mint_reserve_acc;
+    let normal_mint_key = normal_mint_reserve_acc.borrow().normal_mint;
+    let bump = normal_mint_reserve_acc.borrow().bump;
+    let signer_seeds: &[&[u8]] = &[
+        "normal-mint-reserve".as_bytes(),
+        premium_mint_reserve_key.as_ref(),
+        normal_mint_key.as_ref(),
+        &[bump],
+    ];
+
+    // Whatever is left in the vaults goes to one of the treasurer's approved
+    // destinations before they are closed.
+    if normal_token_account.amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                normal_token_account.programs.get("token_program")?,
+                token_interface::TransferChecked {
+                    from: normal_token_account.to_account_info(),
+                    mint: normal_mint.to_account_info(),
+                    authority: normal_mint_reserve_acc
+                        .borrow()
+                        .__account__
+                        .to_account_info(),
+                    to: destination.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            normal_token_account.amount,
+            normal_mint.decimals,
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        normal_token_account.programs.get("token_program")?,
+        token_interface::CloseAccount {
+            account: normal_token_account.to_account_info(),
+            destination: authority.to_account_info(),
+            authority: normal_mint_reserve_acc
+                .borrow()
+                .__account__
+                .to_account_info(),
+        },
+        &[signer_seeds],
+    ))?;
+
+    // Fee vaults belong to the premium reserve, so it signs for this sweep.
+    let premium_mint_key = premium_mint_reserve_acc.borrow().premium_mint;
+    let random_hash = premium_mint_reserve_acc.borrow().random_hash.clone();
+    let premium_bump = premium_mint_reserve_acc.borrow().bump;
+    let premium_signer_seeds: &[&[u8]] = &[
+        "premium-reserve".as_bytes(),
+        premium_mint_key.as_ref(),
+        random_hash.as_bytes(),
+        &[premium_bump],
+    ];
+
+    if normal_fee_account.amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                normal_fee_account.programs.get("token_program")?,
+                token_interface::TransferChecked {
+                    from: normal_fee_account.to_account_info(),
+                    mint: normal_mint.to_account_info(),
+                    authority: premium_mint_reserve_acc
+                        .borrow()
+                        .__account__
+                        .to_account_info(),
+                    to: destination.to_account_info(),
+                },
+                &[premium_signer_seeds],
+            ),
+            normal_fee_account.amount,
+            normal_mint.decimals,
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        normal_fee_account.programs.get("token_program")?,
+        token_interface::CloseAccount {
+            account: normal_fee_account.to_account_info(),
+            destination: authority.to_account_info(),
+            authority: premium_mint_reserve_acc
+                .borrow()
+                .__account__
+                .to_account_info(),
+        },
+        &[premium_signer_seeds],
+    ))?;
+
+    let linked_normal_mints = premium_mint_reserve_acc
+        .borrow()
+        .linked_normal_mints
+        .checked_sub(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().linked_normal_mints,
+        linked_normal_mints
+    );
+
+    Ok(())
+}
+
+pub fn close_premium_mint_reserve_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_fee_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut clock: Sysvar<'info, Clock>,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    // Closing pays the vaults out at once, so like a withdrawal it waits for
+    // any liquidity lock to expire.
+    if !(premium_mint_reserve_acc.borrow().liquidity_locked_until <= clock.unix_timestamp) {
+        return Err(error!(TokenSwapError::LiquidityLocked));
+    }
+
+    if !(premium_mint_reserve_acc.borrow().linked_normal_mints == 0) {
+        return Err(error!(TokenSwapError::NormalReservesStillLinked));
+    }
+
+    // Requests and approvals are PDAs of this reserve; closing it first would
+    // strand their rent and leave them to match a reserve recreated at the
+    // same address. The approval the vaults are swept to is closed along with
+    // the reserve, so it must be the last one.
+    if !(premium_mint_reserve_acc.borrow().open_withdrawal_requests == 0) {
+        return Err(error!(TokenSwapError::WithdrawalsPending));
+    }
+
+    if !(premium_mint_reserve_acc.borrow().approved_destinations == 1) {
+        return Err(error!(TokenSwapError::DestinationsStillApproved));
+    }
+
+    let premium_mint_key = premium_mint_reserve_acc.borrow().premium_mint;
+    let random_hash = premium_mint_reserve_acc.borrow().random_hash.clone();
+    let bump = premium_mint_reserve_acc.borrow().bump;
+    let signer_seeds: &[&[u8]] = &[
+        "premium-reserve".as_bytes(),
+        premium_mint_key.as_ref(),
+        random_hash.as_bytes(),
+        &[bump],
+    ];
+
+    // Whatever is left in the vaults goes to one of the treasurer's approved
+    // destinations before they are closed.
+    if premium_account.amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                premium_account.programs.get("token_program")?,
+                token_interface::TransferChecked {
+                    from: premium_account.to_account_info(),
+                    mint: premium_mint.to_account_info(),
+                    authority: premium_mint_reserve_acc
+                        .borrow()
+                        .__account__
+                        .to_account_info(),
+                    to: destination.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            premium_account.amount,
+            premium_mint.decimals,
+        )?;
+    }
+
+    if premium_fee_account.amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                premium_fee_account.programs.get("token_program")?,
+                token_interface::TransferChecked {
+                    from: premium_fee_account.to_account_info(),
+                    mint: premium_mint.to_account_info(),
+                    authority: premium_mint_reserve_acc
+                        .borrow()
+                        .__account__
+                        .to_account_info(),
+                    to: destination.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            premium_fee_account.amount,
+            premium_mint.decimals,
+        )?;
+    }
+
+    // The reserve holds the mint authority in the minting modes; it goes back
+    // to the creator so the mint isn't orphaned once the reserve is gone.
+    if premium_mint_reserve_acc.borrow().supply_mode != SupplyMode::Vault {
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                premium_mint.programs.get("token_program")?,
+                token_interface::SetAuthority {
+                    current_authority: premium_mint_reserve_acc
+                        .borrow()
+                        .__account__
+                        .to_account_info(),
+                    account_or_mint: premium_mint.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            spl_token_2022::instruction::AuthorityType::MintTokens,
+            Some(premium_mint_reserve_acc.borrow().creator),
+        )?;
+    }
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        premium_account.programs.get("token_program")?,
+        token_interface::CloseAccount {
+            account: premium_account.to_account_info(),
+            destination: authority.to_account_info(),
+            authority: premium_mint_reserve_acc
+                .borrow()
+                .__account__
+                .to_account_info(),
+        },
+        &[signer_seeds],
+    ))?;
+
+    token_interface::close_account(CpiContext::new_with_signer(
+        premium_fee_account.programs.get("token_program")?,
+        token_interface::CloseAccount {
+            account: premium_fee_account.to_account_info(),
+            destination: authority.to_account_info(),
+            authority: premium_mint_reserve_acc
+                .borrow()
+                .__account__
+                .to_account_info(),
+        },
+        &[signer_seeds],
+    ))?;
+
+    Ok(())
+}
+
+pub fn set_normal_mints_cap_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_mints: u32,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    if !(normal_mints >= premium_mint_reserve_acc.borrow().linked_normal_mints) {
+        return Err(error!(TokenSwapError::NormalMintCapBelowLinked));
+    }
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().normal_mints,
+        normal_mints
+    );
+
+    Ok(())
+}
+
+pub fn deposit_premium_tokens_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut premium_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut source: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut amount: u64,
+) -> Result<Deposited> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    let mut amount_in = pull_into_vault(
+        source.programs.get("token_program")?,
+        source.to_account_info(),
+        authority.to_account_info(),
+        &premium_account,
+        &premium_mint,
+        amount,
+        &mut premium_mint_reserve_acc.borrow_mut().total_deposited,
+    )?;
+
+    let event = Deposited {
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        normal_mint_reserve_acc: None,
+        mint: premium_mint_reserve_acc.borrow().premium_mint,
+        source: source.key(),
+        amount: amount_in,
+    };
+
+    Ok(event)
+}
+
+pub fn deposit_normal_tokens_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut normal_mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut source: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut amount: u64,
+) -> Result<Deposited> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    let mut amount_in = pull_into_vault(
+        source.programs.get("token_program")?,
+        source.to_account_info(),
+        authority.to_account_info(),
+        &normal_token_account,
+        &normal_mint,
+        amount,
+        &mut normal_mint_reserve_acc.borrow_mut().total_deposited,
+    )?;
+
+    let event = Deposited {
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        normal_mint_reserve_acc: Some(normal_mint_reserve_acc.borrow().__account__.key()),
+        mint: normal_mint_reserve_acc.borrow().normal_mint,
+        source: source.key(),
+        amount: amount_in,
+    };
+
+    Ok(event)
+}
+
+// The liquidity a vault should hold according to the reserve's counters.
+pub fn expected_vault_balance(
+    total_deposited: u64,
+    total_withdrawn: u64,
+    total_swapped_in: u64,
+    total_swapped_out: u64,
+) -> Result<u64> {
+    total_deposited
+        .checked_add(total_swapped_in)
+        .and_then(|credited| credited.checked_sub(total_withdrawn))
+        .and_then(|credited| credited.checked_sub(total_swapped_out))
+        .ok_or(error!(TokenSwapError::ReserveOutOfBalance))
+}
+
+pub fn reconcile_premium_mint_reserve_handler<'info>(
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+) -> Result<ReserveReconciled> {
+    let expected = expected_vault_balance(
+        premium_mint_reserve_acc.borrow().total_deposited,
+        premium_mint_reserve_acc.borrow().total_withdrawn,
+        premium_mint_reserve_acc.borrow().total_swapped_in,
+        premium_mint_reserve_acc.borrow().total_swapped_out,
+    )?;
+
+    let event = ReserveReconciled {
+        premium_mint_reserve_acc: premium_mint_reserve_acc.borrow().__account__.key(),
+        normal_mint_reserve_acc: None,
+        expected,
+        actual: premium_account.amount,
+    };
+
+    Ok(event)
+}
+
+pub fn reconcile_normal_mint_reserve_handler<'info>(
+    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+) -> Result<ReserveReconciled> {
+    let expected = expected_vault_balance(
+        normal_mint_reserve_acc.borrow().total_deposited,
+        normal_mint_reserve_acc.borrow().total_withdrawn,
+        normal_mint_reserve_acc.borrow().total_swapped_in,
+        normal_mint_reserve_acc.borrow().total_swapped_out,
+    )?;
+
+    let event = ReserveReconciled {
+        premium_mint_reserve_acc: normal_mint_reserve_acc.borrow().premium_mint_reserve_acc,
+        normal_mint_reserve_acc: Some(normal_mint_reserve_acc.borrow().__account__.key()),
+        expected,
+        actual: normal_token_account.amount,
+    };
+
+    Ok(event)
+}
+
+pub fn set_pause_authority_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut pause_authority: Pubkey,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().pause_authority,
+        pause_authority
+    );
+
+    Ok(())
+}
+
+pub fn set_premium_mint_reserve_paused_handler<'info>(
+    mut pause_authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut premium_to_normal_paused: bool,
+    mut normal_to_premium_paused: bool,
+) -> Result<()> {
+    let is_creator = premium_mint_reserve_acc.borrow().creator == pause_authority.key();
+
+    if !(is_creator || premium_mint_reserve_acc.borrow().pause_authority == pause_authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    // The pause authority can only halt swaps; lifting a pause takes the creator.
+    let lifts_pause = (premium_mint_reserve_acc.borrow().premium_to_normal_paused
+        && !premium_to_normal_paused)
+        || (premium_mint_reserve_acc.borrow().normal_to_premium_paused
+            && !normal_to_premium_paused);
+
+    if !(is_creator || !lifts_pause) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    assign!(
+        premium_mint_reserve_acc
+            .borrow_mut()
+            .premium_to_normal_paused,
+        premium_to_normal_paused
+    );
+
+    assign!(
+        premium_mint_reserve_acc
+            .borrow_mut()
+            .normal_to_premium_paused,
+        normal_to_premium_paused
+    );
+
+    Ok(())
+}
+
+pub fn set_normal_mint_reserve_paused_handler<'info>(
+    mut pause_authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut premium_to_normal_paused: bool,
+    mut normal_to_premium_paused: bool,
+) -> Result<()> {
+    let is_creator = premium_mint_reserve_acc.borrow().creator == pause_authority.key();
+
+    if !(is_creator || premium_mint_reserve_acc.borrow().pause_authority == pause_authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    // The pause authority can only halt swaps; lifting a pause takes the creator.
+    let lifts_pause = (normal_mint_reserve_acc.borrow().premium_to_normal_paused
+        && !premium_to_normal_paused)
+        || (normal_mint_reserve_acc.borrow().normal_to_premium_paused && !normal_to_premium_paused);
+
+    if !(is_creator || !lifts_pause) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    assign!(
+        normal_mint_reserve_acc
+            .borrow_mut()
+            .premium_to_normal_paused,
+        premium_to_normal_paused
+    );
+
+    assign!(
+        normal_mint_reserve_acc
+            .borrow_mut()
+            .normal_to_premium_paused,
+        normal_to_premium_paused
+    );
+
+    Ok(())
+}
+
+pub fn set_premium_fee_bps_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut fee_bps: u16,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    if !(fee_bps <= 10_000) {
+        return Err(error!(TokenSwapError::InvalidFee));
+    }
+
+    assign!(premium_mint_reserve_acc.borrow_mut().fee_bps, fee_bps);
+
+    Ok(())
+}
+
+pub fn set_normal_fee_bps_override_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut fee_bps_override: Option<u16>,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    if let Some(fee_bps) = fee_bps_override {
+        if !(fee_bps <= 10_000) {
+            return Err(error!(TokenSwapError::InvalidFee));
+        }
+    }
+
+    assign!(
+        normal_mint_reserve_acc.borrow_mut().fee_bps_override,
+        fee_bps_override
+    );
+
+    Ok(())
+}
+
+pub fn claim_fees_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut fee_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut mint: SeahorseInterfaceAccount<'info, '_, Mint>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut amount: u64,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().treasurer == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    let premium_mint_key = premium_mint_reserve_acc.borrow().premium_mint;
+    let random_hash = premium_mint_reserve_acc.borrow().random_hash.clone();
+    let bump = premium_mint_reserve_acc.borrow().bump;
+    let signer_seeds: &[&[u8]] = &[
+        "premium-reserve".as_bytes(),
+        premium_mint_key.as_ref(),
+        random_hash.as_bytes(),
+        &[bump],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            fee_account.programs.get("token_program")?,
+            token_interface::TransferChecked {
+                from: fee_account.to_account_info(),
+                mint: mint.to_account_info(),
+                authority: premium_mint_reserve_acc
+                    .borrow()
+                    .__account__
+                    .to_account_info(),
+                to: destination.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        amount,
+        mint.decimals,
+    )?;
+
+    Ok(())
+}
+
+pub fn propose_authority_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut new_authority: Option<Pubkey>,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    // Proposing `None` cancels a pending transfer.
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().pending_creator,
+        new_authority
+    );
+
+    Ok(())
+}
+
+pub fn accept_authority_handler<'info>(
+    mut new_authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+) -> Result<()> {
+    let pending_creator = match premium_mint_reserve_acc.borrow().pending_creator {
+        Some(pending_creator) => pending_creator,
+        None => return Err(error!(TokenSwapError::NoPendingAuthority)),
+    };
+
+    if !(pending_creator == new_authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    // Normal reserves defer to the premium reserve's creator, so they follow
+    // it without being touched here.
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().creator,
+        new_authority.key()
+    );
+
+    assign!(premium_mint_reserve_acc.borrow_mut().pending_creator, None);
+
+    // The pause role moves with control, so the outgoing creator can't halt
+    // trading that only the new creator could resume.
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().pause_authority,
+        new_authority.key()
+    );
+
+    // Likewise the treasurer, and the approvals the outgoing creator made are
+    // retired so pending withdrawals can't still reach their accounts.
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().treasurer,
+        new_authority.key()
+    );
+
+    let destination_epoch = premium_mint_reserve_acc
+        .borrow()
+        .destination_epoch
+        .checked_add(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().destination_epoch,
+        destination_epoch
+    );
+
+    Ok(())
+}
+
+pub fn set_treasurer_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut treasurer: Pubkey,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    assign!(premium_mint_reserve_acc.borrow_mut().treasurer, treasurer);
+
+    Ok(())
+}
+
+pub fn approve_destination_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut approved_destination: Empty<Mutable<LoadedApprovedDestination<'info, '_>>>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+) -> Result<()> {
+    let mut bump = approved_destination
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
+    let mut approved_destination = approved_destination.account.clone();
+
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    assign!(
+        approved_destination.borrow_mut().premium_mint_reserve_acc,
+        premium_mint_reserve_acc.borrow().__account__.key()
+    );
+
+    assign!(
+        approved_destination.borrow_mut().destination,
+        destination.key()
+    );
+
+    assign!(approved_destination.borrow_mut().bump, bump);
+
+    assign!(
+        approved_destination.borrow_mut().epoch,
+        premium_mint_reserve_acc.borrow().destination_epoch
+    );
+
+    let approved_destinations = premium_mint_reserve_acc
+        .borrow()
+        .approved_destinations
+        .checked_add(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().approved_destinations,
+        approved_destinations
+    );
+
+    Ok(())
+}
+
+pub fn revoke_destination_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    let approved_destinations = premium_mint_reserve_acc
+        .borrow()
+        .approved_destinations
+        .checked_sub(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().approved_destinations,
+        approved_destinations
+    );
+
+    Ok(())
+}
+
+pub fn request_normal_withdrawal_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_token_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut withdrawal_request: Empty<Mutable<LoadedWithdrawalRequest<'info, '_>>>,
+    mut clock: Sysvar<'info, Clock>,
+    mut amount: u64,
+) -> Result<()> {
+    let mut bump = withdrawal_request
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
+    let mut withdrawal_request = withdrawal_request.account.clone();
+
+    open_withdrawal_request(
+        &authority,
+        &premium_mint_reserve_acc,
+        normal_token_account.key(),
+        destination.key(),
+        &withdrawal_request,
+        clock.unix_timestamp,
+        amount,
+        bump,
+    )
+}
+
+pub fn request_premium_withdrawal_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut premium_account: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut destination: SeahorseInterfaceAccount<'info, '_, TokenAccount>,
+    mut withdrawal_request: Empty<Mutable<LoadedWithdrawalRequest<'info, '_>>>,
+    mut clock: Sysvar<'info, Clock>,
+    mut amount: u64,
+) -> Result<()> {
+    let mut bump = withdrawal_request
+        .bump
+        .ok_or(error!(TokenSwapError::MissingBump))?;
+    let mut withdrawal_request = withdrawal_request.account.clone();
+
+    open_withdrawal_request(
+        &authority,
+        &premium_mint_reserve_acc,
+        premium_account.key(),
+        destination.key(),
+        &withdrawal_request,
+        clock.unix_timestamp,
+        amount,
+        bump,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn open_withdrawal_request<'info>(
+    authority: &SeahorseSigner<'info, '_>,
+    premium_mint_reserve_acc: &Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    vault: Pubkey,
+    destination: Pubkey,
+    withdrawal_request: &Mutable<LoadedWithdrawalRequest<'info, '_>>,
+    now: i64,
+    amount: u64,
+    bump: u8,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().treasurer == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    if !(premium_mint_reserve_acc.borrow().liquidity_locked_until <= now) {
+        return Err(error!(TokenSwapError::LiquidityLocked));
+    }
+
+    // Reserves created before the minimum existed must raise their delay
+    // before they can announce withdrawals.
+    check_withdrawal_delay(premium_mint_reserve_acc.borrow().withdrawal_delay)?;
+
+    let executable_ts = now
+        .checked_add(premium_mint_reserve_acc.borrow().withdrawal_delay)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        withdrawal_request.borrow_mut().premium_mint_reserve_acc,
+        premium_mint_reserve_acc.borrow().__account__.key()
+    );
+
+    assign!(withdrawal_request.borrow_mut().vault, vault);
+
+    assign!(withdrawal_request.borrow_mut().destination, destination);
+
+    assign!(withdrawal_request.borrow_mut().amount, amount);
+
+    assign!(withdrawal_request.borrow_mut().executable_ts, executable_ts);
+
+    assign!(withdrawal_request.borrow_mut().bump, bump);
+
+    let open_withdrawal_requests = premium_mint_reserve_acc
+        .borrow()
+        .open_withdrawal_requests
+        .checked_add(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc
+            .borrow_mut()
+            .open_withdrawal_requests,
+        open_withdrawal_requests
+    );
+
+    Ok(())
+}
+
+pub fn cancel_withdrawal_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().treasurer == authority.key()
+        || premium_mint_reserve_acc.borrow().creator == authority.key())
+    {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    let open_withdrawal_requests = premium_mint_reserve_acc
+        .borrow()
+        .open_withdrawal_requests
+        .checked_sub(1)
+        .ok_or(error!(TokenSwapError::MathOverflow))?;
+
+    assign!(
+        premium_mint_reserve_acc
+            .borrow_mut()
+            .open_withdrawal_requests,
+        open_withdrawal_requests
+    );
+
+    Ok(())
+}
+
+pub fn set_withdrawal_delay_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut withdrawal_delay: i64,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    // Shortening the delay would let a fresh request skip the notice users
+    // were relying on.
+    if !(withdrawal_delay >= premium_mint_reserve_acc.borrow().withdrawal_delay) {
+        return Err(error!(TokenSwapError::TimelockShortened));
+    }
+
+    check_withdrawal_delay(withdrawal_delay)?;
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().withdrawal_delay,
+        withdrawal_delay
+    );
+
+    Ok(())
+}
+
+pub fn lock_liquidity_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut locked_until: i64,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    if !(locked_until >= premium_mint_reserve_acc.borrow().liquidity_locked_until) {
+        return Err(error!(TokenSwapError::TimelockShortened));
+    }
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().liquidity_locked_until,
+        locked_until
+    );
+
+    Ok(())
+}
+
+pub fn update_schedule_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut clock: Sysvar<'info, Clock>,
+    mut go_live_timestamp: i64,
+    mut end_ts: Option<i64>,
+    mut sunset_ts: Option<i64>,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    check_schedule(go_live_timestamp, end_ts, sunset_ts)?;
+
+    check_schedule_keeps_live_swaps(
+        premium_mint_reserve_acc.borrow().go_live_timestamp,
+        premium_mint_reserve_acc.borrow().end_ts,
+        premium_mint_reserve_acc.borrow().sunset_ts,
+        go_live_timestamp,
+        end_ts,
+        sunset_ts,
+        premium_mint_reserve_acc.borrow().grace_direction,
+        premium_mint_reserve_acc.borrow().premium_to_normal_paused,
+        premium_mint_reserve_acc.borrow().normal_to_premium_paused,
+        clock.unix_timestamp,
+    )?;
+
+    assign!(
+        premium_mint_reserve_acc.borrow_mut().go_live_timestamp,
+        go_live_timestamp
+    );
+
+    assign!(premium_mint_reserve_acc.borrow_mut().end_ts, end_ts);
+
+    assign!(premium_mint_reserve_acc.borrow_mut().sunset_ts, sunset_ts);
+
+    Ok(())
+}
+
+pub fn update_normal_schedule_handler<'info>(
+    mut authority: SeahorseSigner<'info, '_>,
+    mut premium_mint_reserve_acc: Mutable<LoadedPremiumMintReserve<'info, '_>>,
+    mut normal_mint_reserve_acc: Mutable<LoadedNormalMintReserve<'info, '_>>,
+    mut clock: Sysvar<'info, Clock>,
+    mut go_live_ts: i64,
+    mut end_ts: Option<i64>,
+    mut sunset_ts: Option<i64>,
+) -> Result<()> {
+    if !(premium_mint_reserve_acc.borrow().creator == authority.key()) {
+        return Err(error!(TokenSwapError::InvalidAuthority));
+    }
+
+    check_schedule(go_live_ts, end_ts, sunset_ts)?;
+
+    check_schedule_keeps_live_swaps(
+        normal_mint_reserve_acc.borrow().go_live_ts,
+        normal_mint_reserve_acc.borrow().end_ts,
+        normal_mint_reserve_acc.borrow().sunset_ts,
+        go_live_ts,
+        end_ts,
+        sunset_ts,
+        normal_mint_reserve_acc.borrow().grace_direction,
+        normal_mint_reserve_acc.borrow().premium_to_normal_paused,
+        normal_mint_reserve_acc.borrow().normal_to_premium_paused,
+        clock.unix_timestamp,
+    )?;
+
+    assign!(normal_mint_reserve_acc.borrow_mut().go_live_ts, go_live_ts);
+
+    assign!(normal_mint_reserve_acc.borrow_mut().end_ts, end_ts);
+
+    assign!(normal_mint_reserve_acc.borrow_mut().sunset_ts, sunset_ts);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_between_mints_scales_up_to_more_decimals() {
+        assert_eq!(
+            convert_between_mints(1_000_000, 1, 1, 6, 9, RoundingPolicy::Down),
+            Ok(1_000_000_000)
+        );
+        assert_eq!(
+            convert_between_mints(1_000_000, 3, 2, 6, 9, RoundingPolicy::Down),
+            Ok(1_500_000_000)
+        );
+    }
+
+    #[test]
+    fn convert_between_mints_scales_down_to_fewer_decimals() {
+        assert_eq!(
+            convert_between_mints(1_000_000_000, 1, 1, 9, 6, RoundingPolicy::Down),
+            Ok(1_000_000)
+        );
+        assert_eq!(
+            convert_between_mints(1_000_000_000, 2, 1, 9, 6, RoundingPolicy::Up),
+            Ok(2_000_000)
+        );
+    }
+
+    #[test]
+    fn convert_between_mints_rounds_within_the_loss_bound() {
+        // 1_000_001 raw units at 9 decimals are 1000.001 at 6.
+        assert_eq!(
+            convert_between_mints(1_000_001, 1, 1, 9, 6, RoundingPolicy::Down),
+            Ok(1000)
+        );
+        assert_eq!(
+            convert_between_mints(1_000_001, 1, 1, 9, 6, RoundingPolicy::Up),
+            Ok(1001)
+        );
+    }
+
+    #[test]
+    fn convert_between_mints_rejects_rounding_past_the_loss_bound() {
+        // 1.999 rounds down to 1, losing about half the output.
+        assert_eq!(
+            convert_between_mints(1_999, 1, 1, 9, 6, RoundingPolicy::Down),
+            Err(error!(TokenSwapError::ConversionPrecisionLoss))
+        );
+        // 10.001 rounds up to 11, gaining about a tenth.
+        assert_eq!(
+            convert_between_mints(10_001, 1, 1, 9, 6, RoundingPolicy::Up),
+            Err(error!(TokenSwapError::ConversionPrecisionLoss))
+        );
+    }
+
+    #[test]
+    fn convert_between_mints_rejects_zero_output() {
+        assert_eq!(
+            convert_between_mints(999, 1, 1, 9, 6, RoundingPolicy::Down),
+            Err(error!(TokenSwapError::ConversionPrecisionLoss))
+        );
+        assert_eq!(
+            convert_between_mints(0, 1, 1, 9, 6, RoundingPolicy::Down),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn convert_between_mints_rejects_overflow() {
+        assert_eq!(
+            convert_between_mints(u64::MAX, 2, 1, 6, 6, RoundingPolicy::Down),
+            Err(error!(TokenSwapError::MathOverflow))
+        );
+        assert_eq!(
+            convert_between_mints(1, 1, 1, 0, 255, RoundingPolicy::Down),
+            Err(error!(TokenSwapError::MathOverflow))
+        );
+    }
+
+    #[test]
+    fn check_schedule_accepts_ordered_or_open_ended_schedules() {
+        assert_eq!(check_schedule(100, None, None), Ok(()));
+        assert_eq!(check_schedule(100, Some(200), None), Ok(()));
+        assert_eq!(check_schedule(100, None, Some(200)), Ok(()));
+        assert_eq!(check_schedule(100, Some(200), Some(300)), Ok(()));
+        assert_eq!(check_schedule(100, Some(200), Some(200)), Ok(()));
+    }
+
+    #[test]
+    fn check_schedule_rejects_times_at_or_before_go_live() {
+        assert_eq!(
+            check_schedule(100, Some(100), None),
+            Err(error!(TokenSwapError::InvalidSchedule))
+        );
+        assert_eq!(
+            check_schedule(100, None, Some(50)),
+            Err(error!(TokenSwapError::InvalidSchedule))
+        );
+    }
+
+    #[test]
+    fn check_schedule_rejects_a_sunset_before_the_end() {
+        assert_eq!(
+            check_schedule(100, Some(300), Some(200)),
+            Err(error!(TokenSwapError::InvalidSchedule))
+        );
+    }
+
+    #[test]
+    fn swap_window_open_without_a_schedule() {
+        assert!(swap_window_open(
+            None,
+            None,
+            SwapDirection::PremiumToNormal,
+            SwapDirection::NormalToPremium,
+            i64::MAX
+        ));
+    }
+
+    #[test]
+    fn swap_window_closes_at_end_outside_the_grace_direction() {
+        let grace = SwapDirection::PremiumToNormal;
+        let other = SwapDirection::NormalToPremium;
+
+        assert!(swap_window_open(Some(200), Some(300), grace, other, 199));
+        assert!(!swap_window_open(Some(200), Some(300), grace, other, 200));
+        assert_eq!(
+            check_swap_window(Some(200), Some(300), grace, other, 200),
+            Err(error!(TokenSwapError::SwapWindowClosed))
+        );
+    }
+
+    #[test]
+    fn swap_window_keeps_the_grace_direction_open_until_sunset() {
+        let grace = SwapDirection::PremiumToNormal;
+
+        assert!(swap_window_open(Some(200), Some(300), grace, grace, 299));
+        assert!(!swap_window_open(Some(200), Some(300), grace, grace, 300));
+        assert!(swap_window_open(Some(200), None, grace, grace, i64::MAX));
+    }
+
+    #[test]
+    fn swap_window_closes_both_directions_at_sunset_without_an_end() {
+        let grace = SwapDirection::PremiumToNormal;
+        let other = SwapDirection::NormalToPremium;
+
+        assert!(swap_window_open(None, Some(300), grace, other, 299));
+        assert!(!swap_window_open(None, Some(300), grace, other, 300));
+        assert!(!swap_window_open(None, Some(300), grace, grace, 300));
+    }
+
+    #[test]
+    fn schedule_change_may_end_swaps_that_are_not_live() {
+        let grace = SwapDirection::PremiumToNormal;
+
+        // Not live yet.
+        assert_eq!(
+            check_schedule_keeps_live_swaps(
+                200,
+                None,
+                None,
+                200,
+                Some(250),
+                Some(250),
+                grace,
+                false,
+                false,
+                150
+            ),
+            Ok(())
+        );
+        // Already past the sunset.
+        assert_eq!(
+            check_schedule_keeps_live_swaps(
+                100,
+                Some(200),
+                Some(300),
+                100,
+                Some(150),
+                Some(150),
+                grace,
+                false,
+                false,
+                400
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn schedule_change_may_not_end_live_swaps() {
+        let grace = SwapDirection::PremiumToNormal;
+
+        // Moving the end into the past stops normal-to-premium swaps.
+        assert_eq!(
+            check_schedule_keeps_live_swaps(
+                100,
+                None,
+                None,
+                100,
+                Some(150),
+                Some(300),
+                grace,
+                false,
+                false,
+                200
+            ),
+            Err(error!(TokenSwapError::ScheduleStopsLiveSwaps))
+        );
+        // Pushing go-live back into the future stops both directions.
+        assert_eq!(
+            check_schedule_keeps_live_swaps(
+                100, None, None, 300, None, None, grace, true, false, 200
+            ),
+            Err(error!(TokenSwapError::ScheduleStopsLiveSwaps))
+        );
+    }
+
+    #[test]
+    fn schedule_change_may_end_paused_swaps() {
+        let grace = SwapDirection::PremiumToNormal;
+
+        // Only normal-to-premium closes at the new end, and it is paused.
+        assert_eq!(
+            check_schedule_keeps_live_swaps(
+                100,
+                None,
+                None,
+                100,
+                Some(150),
+                Some(300),
+                grace,
+                false,
+                true,
+                200
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_schedule_keeps_live_swaps(
+                100, None, None, 300, None, None, grace, true, true, 200
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn random_hash_seed_accepts_hashes_up_to_the_seed_limit() {
+        let hash = "a".repeat(MAX_RANDOM_HASH_LEN);
+
+        assert_eq!(random_hash_seed(&hash), Ok(hash.as_bytes()));
+        assert_eq!(random_hash_seed(""), Ok(&b""[..]));
+    }
+
+    #[test]
+    fn random_hash_seed_rejects_hashes_past_the_seed_limit() {
+        let hash = "a".repeat(MAX_RANDOM_HASH_LEN + 1);
+
+        assert_eq!(
+            random_hash_seed(&hash),
+            Err(error!(TokenSwapError::RandomHashTooLong))
+        );
+        // The limit is in bytes, not characters.
+        assert_eq!(
+            random_hash_seed(&"é".repeat(17)),
+            Err(error!(TokenSwapError::RandomHashTooLong))
+        );
+    }
+
+    #[test]
+    fn check_withdrawal_delay_enforces_the_minimum() {
+        assert_eq!(check_withdrawal_delay(MIN_WITHDRAWAL_DELAY), Ok(()));
+        assert_eq!(check_withdrawal_delay(MIN_WITHDRAWAL_DELAY + 1), Ok(()));
+        assert_eq!(
+            check_withdrawal_delay(MIN_WITHDRAWAL_DELAY - 1),
+            Err(error!(TokenSwapError::WithdrawalDelayTooShort))
+        );
+        assert_eq!(
+            check_withdrawal_delay(0),
+            Err(error!(TokenSwapError::WithdrawalDelayTooShort))
+        );
+    }
+
+    // Packs an initialized Token-2022 mint carrying `extensions`, leaving
+    // their values zeroed.
+    fn token_2022_mint_data(extensions: &[(u16, usize)]) -> Vec<u8> {
+        let mut data = vec![0; spl_token_2022::state::Account::LEN + 1];
+        let mint = spl_token_2022::state::Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+
+        spl_token_2022::state::Mint::pack(mint, &mut data[..spl_token_2022::state::Mint::LEN])
+            .unwrap();
+        data[spl_token_2022::state::Account::LEN] = 1;
+
+        for (extension_type, length) in extensions {
+            data.extend_from_slice(&extension_type.to_le_bytes());
+            data.extend_from_slice(&(*length as u16).to_le_bytes());
+            data.extend(vec![0; *length]);
+        }
+
+        data
+    }
+
+    fn check_mint_data(owner: Pubkey, mut data: Vec<u8>) -> Result<()> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mint = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        check_mint_extensions(&mint)
+    }
+
+    #[test]
+    fn check_mint_extensions_rejects_unsafe_extensions() {
+        let permanent_delegate = (u16::from(ExtensionType::PermanentDelegate), 32);
+        let transfer_hook = (TRANSFER_HOOK_EXTENSION, 64);
+
+        assert_eq!(
+            check_mint_data(spl_token_2022::ID, token_2022_mint_data(&[transfer_hook])),
+            Err(error!(TokenSwapError::TransferHookMint))
+        );
+        assert_eq!(
+            check_mint_data(
+                spl_token_2022::ID,
+                token_2022_mint_data(&[permanent_delegate])
+            ),
+            Err(error!(TokenSwapError::PermanentDelegateMint))
+        );
+        assert_eq!(
+            check_mint_data(
+                spl_token_2022::ID,
+                token_2022_mint_data(&[(u16::from(ExtensionType::MintCloseAuthority), 32)])
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_mint_data(
+                anchor_spl::token::ID,
+                token_2022_mint_data(&[transfer_hook])
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn is_native_mint_accepts_both_token_programs() {
+        assert!(is_native_mint(
+            anchor_spl::token::spl_token::native_mint::ID
+        ));
+        assert!(is_native_mint(spl_token_2022::native_mint::ID));
+        assert!(!is_native_mint(Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn normal_to_normal_rate_converts_in_one_step() {
+        let (numerator, denominator) = normal_to_normal_rate(2, 1, 3, 1);
+
+        assert_eq!(
+            convert_between_mints(
+                1_000_000,
+                numerator,
+                denominator,
+                6,
+                9,
+                RoundingPolicy::Down
+            ),
+            Ok(1_500_000_000)
+        );
+
+        // Going through premium would round 1 raw unit down to zero first.
+        let (numerator, denominator) = normal_to_normal_rate(3, 1, 3, 1);
+
+        assert_eq!(
+            convert_between_mints(1, numerator, denominator, 0, 0, RoundingPolicy::Down),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn expected_vault_balance_nets_credits_against_debits() {
+        assert_eq!(expected_vault_balance(100, 30, 50, 20), Ok(100));
+        assert_eq!(expected_vault_balance(0, 0, 0, 0), Ok(0));
+    }
+
+    #[test]
+    fn expected_vault_balance_adds_swaps_in_before_subtracting() {
+        // More is withdrawn than was deposited, covered by swap inflows.
+        assert_eq!(expected_vault_balance(10, 40, 50, 0), Ok(20));
+    }
+
+    #[test]
+    fn expected_vault_balance_rejects_negative_balances() {
+        assert_eq!(
+            expected_vault_balance(10, 11, 0, 0),
+            Err(error!(TokenSwapError::ReserveOutOfBalance))
+        );
+        assert_eq!(
+            expected_vault_balance(10, 0, 0, 11),
+            Err(error!(TokenSwapError::ReserveOutOfBalance))
+        );
+    }
+
+    #[test]
+    fn expected_vault_balance_rejects_overflowing_credits() {
+        assert_eq!(
+            expected_vault_balance(u64::MAX, 0, 1, 1),
+            Err(error!(TokenSwapError::ReserveOutOfBalance))
+        );
+    }
 }
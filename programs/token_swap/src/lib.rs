@@ -1,13 +1,28 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 #![allow(unused_mut)]
+// Lint policy: the crate builds with `clippy -D warnings`. Lints are only
+// allowed crate-wide when they flag patterns every handler or entrypoint
+// shares; anything narrower is allowed on the item that needs it.
+//
+// Idioms of the Seahorse-generated code, kept as-is across the crate:
+// `if !(cond)` checks, `return Ok(())` entrypoints, `.as_ref()` seeds,
+// `.map(|bump| *bump)` bump lookups and clones of `Copy` fields.
+#![allow(clippy::clone_on_copy)]
+#![allow(clippy::map_clone)]
+#![allow(clippy::needless_return)]
+#![allow(clippy::nonminimal_bool)]
+#![allow(clippy::useless_asref)]
+// Anchor's error type and macros, not something this crate can change.
+#![allow(clippy::result_large_err)]
+#![allow(unexpected_cfgs)]
 
 pub mod dot;
 
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
 };
 
 use dot::program::*;
@@ -87,8 +102,11 @@ pub mod seahorse_util {
     pub struct ProgramsMap<'info>(pub HashMap<&'static str, AccountInfo<'info>>);
 
     impl<'info> ProgramsMap<'info> {
-        pub fn get(&self, name: &'static str) -> AccountInfo<'info> {
-            self.0.get(name).unwrap().clone()
+        pub fn get(&self, name: &'static str) -> Result<AccountInfo<'info>> {
+            self.0
+                .get(name)
+                .cloned()
+                .ok_or(error!(dot::program::TokenSwapError::MissingProgram))
         }
     }
 
@@ -102,13 +120,16 @@ pub mod seahorse_util {
         type Target = A;
 
         fn deref(&self) -> &Self::Target {
-            &self.account
+            self.account
         }
     }
 
     pub type SeahorseAccount<'info, 'entrypoint, A> =
         WithPrograms<'info, 'entrypoint, Box<Account<'info, A>>>;
 
+    pub type SeahorseInterfaceAccount<'info, 'entrypoint, A> =
+        WithPrograms<'info, 'entrypoint, Box<InterfaceAccount<'info, A>>>;
+
     pub type SeahorseSigner<'info, 'entrypoint> = WithPrograms<'info, 'entrypoint, Signer<'info>>;
 
     #[derive(Clone, Debug)]
@@ -146,23 +167,31 @@ mod token_swap {
     use seahorse_util::*;
     use std::collections::HashMap;
 
+    #[event_cpi]
     #[derive(Accounts)]
-    # [instruction (amount : u64)]
     pub struct WithdrawNormalTokens<'info> {
         #[account(mut)]
         pub authority: Signer<'info>,
-        #[account(mut)]
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump)]
         pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
-        #[account(mut)]
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , has_one = normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
         pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
         #[account(mut)]
-        pub normal_token_account: Box<Account<'info, TokenAccount>>,
-        #[account(mut)]
-        pub destination: Box<Account<'info, TokenAccount>>,
-        pub token_program: Program<'info, Token>,
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = destination . mint == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (seeds = ["approved-destination" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , destination . key () . as_ref () , premium_mint_reserve_acc . destination_epoch . to_le_bytes () . as_ref ()] , bump = approved_destination . bump)]
+        pub approved_destination: Box<Account<'info, dot::program::ApprovedDestination>>,
+        # [account (mut , seeds = ["withdrawal-request" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_token_account . key () . as_ref ()] , bump = withdrawal_request . bump , has_one = destination @ dot :: program :: TokenSwapError :: WithdrawalRequestMismatch , close = authority)]
+        pub withdrawal_request: Box<Account<'info, dot::program::WithdrawalRequest>>,
+        pub clock: Sysvar<'info, Clock>,
+        # [account (constraint = normal_mint . key () == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidNormalMint)]
+        pub normal_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = token_program . key () == normal_mint_reserve_acc . normal_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub token_program: Interface<'info, TokenInterface>,
     }
 
-    pub fn withdraw_normal_tokens(ctx: Context<WithdrawNormalTokens>, amount: u64) -> Result<()> {
+    pub fn withdraw_normal_tokens(ctx: Context<WithdrawNormalTokens>) -> Result<()> {
         let mut programs = HashMap::new();
 
         programs.insert(
@@ -186,63 +215,96 @@ mod token_swap {
             &programs_map,
         );
 
-        let normal_token_account = SeahorseAccount {
+        let normal_token_account = SeahorseInterfaceAccount {
             account: &ctx.accounts.normal_token_account,
             programs: &programs_map,
         };
 
-        let destination = SeahorseAccount {
+        let destination = SeahorseInterfaceAccount {
             account: &ctx.accounts.destination,
             programs: &programs_map,
         };
 
-        withdraw_normal_tokens_handler(
+        let withdrawal_request = dot::program::WithdrawalRequest::load(
+            &mut ctx.accounts.withdrawal_request,
+            &programs_map,
+        );
+
+        let clock = &ctx.accounts.clock.clone();
+
+        let normal_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_mint,
+            programs: &programs_map,
+        };
+
+        let event = withdraw_normal_tokens_handler(
             authority.clone(),
             premium_mint_reserve_acc.clone(),
             normal_mint_reserve_acc.clone(),
             normal_token_account.clone(),
+            normal_mint.clone(),
             destination.clone(),
-            amount,
-        );
+            withdrawal_request.clone(),
+            clock.clone(),
+        )?;
 
         dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
 
         dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
 
+        emit_cpi!(event);
+
         return Ok(());
     }
 
+    #[event_cpi]
     #[derive(Accounts)]
-    # [instruction (amount : u64)]
+    # [instruction (amount : u64 , min_amount_out : u64 , deadline : Option < i64 >)]
     pub struct SwapNormalTokensForPremiumTokens<'info> {
         #[account(mut)]
         pub source_authority: Signer<'info>,
-        #[account(mut)]
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump , has_one = premium_account @ dot :: program :: TokenSwapError :: InvalidPremiumTokenAccount)]
         pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
-        #[account(mut)]
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , has_one = normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
         pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
         #[account(mut)]
-        pub normal_token_account: Box<Account<'info, TokenAccount>>,
-        #[account(mut)]
-        pub premium_account: Box<Account<'info, TokenAccount>>,
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
         #[account(mut)]
-        pub source: Box<Account<'info, TokenAccount>>,
-        #[account(mut)]
-        pub destination: Box<Account<'info, TokenAccount>>,
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = source . mint == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidSourceMint)]
+        pub source: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = destination . mint == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref ()] , bump)]
+        pub fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
         #[account(mut)]
         pub clock: Sysvar<'info, Clock>,
-        pub token_program: Program<'info, Token>,
+        # [account (mut , constraint = premium_mint . key () == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidPremiumMint)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (mut , constraint = normal_mint . key () == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidNormalMint)]
+        pub normal_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = premium_token_program . key () == premium_mint_reserve_acc . premium_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub premium_token_program: Interface<'info, TokenInterface>,
+        # [account (constraint = normal_token_program . key () == normal_mint_reserve_acc . normal_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub normal_token_program: Interface<'info, TokenInterface>,
     }
 
     pub fn swap_normal_tokens_for_premium_tokens(
         ctx: Context<SwapNormalTokensForPremiumTokens>,
         amount: u64,
+        min_amount_out: u64,
+        deadline: Option<i64>,
     ) -> Result<()> {
         let mut programs = HashMap::new();
 
         programs.insert(
-            "token_program",
-            ctx.accounts.token_program.to_account_info(),
+            "premium_token_program",
+            ctx.accounts.premium_token_program.to_account_info(),
+        );
+
+        programs.insert(
+            "normal_token_program",
+            ctx.accounts.normal_token_program.to_account_info(),
         );
 
         let programs_map = ProgramsMap(programs);
@@ -261,29 +323,44 @@ mod token_swap {
             &programs_map,
         );
 
-        let normal_token_account = SeahorseAccount {
+        let normal_token_account = SeahorseInterfaceAccount {
             account: &ctx.accounts.normal_token_account,
             programs: &programs_map,
         };
 
-        let premium_account = SeahorseAccount {
+        let premium_account = SeahorseInterfaceAccount {
             account: &ctx.accounts.premium_account,
             programs: &programs_map,
         };
 
-        let source = SeahorseAccount {
+        let source = SeahorseInterfaceAccount {
             account: &ctx.accounts.source,
             programs: &programs_map,
         };
 
-        let destination = SeahorseAccount {
+        let destination = SeahorseInterfaceAccount {
             account: &ctx.accounts.destination,
             programs: &programs_map,
         };
 
+        let fee_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.fee_account,
+            programs: &programs_map,
+        };
+
         let clock = &ctx.accounts.clock.clone();
 
-        swap_normal_tokens_for_premium_tokens_handler(
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let normal_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_mint,
+            programs: &programs_map,
+        };
+
+        let event = swap_normal_tokens_for_premium_tokens_handler(
             source_authority.clone(),
             premium_mint_reserve_acc.clone(),
             normal_mint_reserve_acc.clone(),
@@ -291,32 +368,47 @@ mod token_swap {
             premium_account.clone(),
             source.clone(),
             destination.clone(),
+            fee_account.clone(),
+            premium_mint.clone(),
+            normal_mint.clone(),
             clock.clone(),
             amount,
-        );
+            min_amount_out,
+            deadline,
+        )?;
 
         dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
 
         dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
 
+        emit_cpi!(event);
+
         return Ok(());
     }
 
+    #[event_cpi]
     #[derive(Accounts)]
-    # [instruction (amount : u64)]
     pub struct WithdrawPremiumTokens<'info> {
         #[account(mut)]
         pub authority: Signer<'info>,
-        #[account(mut)]
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump , has_one = premium_account @ dot :: program :: TokenSwapError :: InvalidPremiumTokenAccount)]
         pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
         #[account(mut)]
-        pub premium_account: Box<Account<'info, TokenAccount>>,
-        #[account(mut)]
-        pub destination: Box<Account<'info, TokenAccount>>,
-        pub token_program: Program<'info, Token>,
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = destination . mint == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (seeds = ["approved-destination" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , destination . key () . as_ref () , premium_mint_reserve_acc . destination_epoch . to_le_bytes () . as_ref ()] , bump = approved_destination . bump)]
+        pub approved_destination: Box<Account<'info, dot::program::ApprovedDestination>>,
+        # [account (mut , seeds = ["withdrawal-request" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , premium_account . key () . as_ref ()] , bump = withdrawal_request . bump , has_one = destination @ dot :: program :: TokenSwapError :: WithdrawalRequestMismatch , close = authority)]
+        pub withdrawal_request: Box<Account<'info, dot::program::WithdrawalRequest>>,
+        pub clock: Sysvar<'info, Clock>,
+        # [account (constraint = premium_mint . key () == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidPremiumMint)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = token_program . key () == premium_mint_reserve_acc . premium_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub token_program: Interface<'info, TokenInterface>,
     }
 
-    pub fn withdraw_premium_tokens(ctx: Context<WithdrawPremiumTokens>, amount: u64) -> Result<()> {
+    pub fn withdraw_premium_tokens(ctx: Context<WithdrawPremiumTokens>) -> Result<()> {
         let mut programs = HashMap::new();
 
         programs.insert(
@@ -335,60 +427,93 @@ mod token_swap {
             &programs_map,
         );
 
-        let premium_account = SeahorseAccount {
+        let premium_account = SeahorseInterfaceAccount {
             account: &ctx.accounts.premium_account,
             programs: &programs_map,
         };
 
-        let destination = SeahorseAccount {
+        let destination = SeahorseInterfaceAccount {
             account: &ctx.accounts.destination,
             programs: &programs_map,
         };
 
-        withdraw_premium_tokens_handler(
+        let withdrawal_request = dot::program::WithdrawalRequest::load(
+            &mut ctx.accounts.withdrawal_request,
+            &programs_map,
+        );
+
+        let clock = &ctx.accounts.clock.clone();
+
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let event = withdraw_premium_tokens_handler(
             authority.clone(),
             premium_mint_reserve_acc.clone(),
             premium_account.clone(),
+            premium_mint.clone(),
             destination.clone(),
-            amount,
-        );
+            withdrawal_request.clone(),
+            clock.clone(),
+        )?;
 
         dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
 
+        emit_cpi!(event);
+
         return Ok(());
     }
 
+    #[event_cpi]
     #[derive(Accounts)]
-    # [instruction (amount : u64)]
+    # [instruction (amount : u64 , min_amount_out : u64 , deadline : Option < i64 >)]
     pub struct SwapPremiumTokensForNormalTokens<'info> {
         #[account(mut)]
         pub source_authority: Signer<'info>,
-        #[account(mut)]
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump , has_one = premium_account @ dot :: program :: TokenSwapError :: InvalidPremiumTokenAccount)]
         pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
-        #[account(mut)]
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , has_one = normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
         pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
         #[account(mut)]
-        pub normal_token_account: Box<Account<'info, TokenAccount>>,
-        #[account(mut)]
-        pub premium_account: Box<Account<'info, TokenAccount>>,
-        #[account(mut)]
-        pub source: Box<Account<'info, TokenAccount>>,
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
         #[account(mut)]
-        pub destination: Box<Account<'info, TokenAccount>>,
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = source . mint == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidSourceMint)]
+        pub source: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = destination . mint == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump)]
+        pub fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
         #[account(mut)]
         pub clock: Sysvar<'info, Clock>,
-        pub token_program: Program<'info, Token>,
+        # [account (mut , constraint = premium_mint . key () == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidPremiumMint)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (mut , constraint = normal_mint . key () == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidNormalMint)]
+        pub normal_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = premium_token_program . key () == premium_mint_reserve_acc . premium_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub premium_token_program: Interface<'info, TokenInterface>,
+        # [account (constraint = normal_token_program . key () == normal_mint_reserve_acc . normal_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub normal_token_program: Interface<'info, TokenInterface>,
     }
 
     pub fn swap_premium_tokens_for_normal_tokens(
         ctx: Context<SwapPremiumTokensForNormalTokens>,
         amount: u64,
+        min_amount_out: u64,
+        deadline: Option<i64>,
     ) -> Result<()> {
         let mut programs = HashMap::new();
 
         programs.insert(
-            "token_program",
-            ctx.accounts.token_program.to_account_info(),
+            "premium_token_program",
+            ctx.accounts.premium_token_program.to_account_info(),
+        );
+
+        programs.insert(
+            "normal_token_program",
+            ctx.accounts.normal_token_program.to_account_info(),
         );
 
         let programs_map = ProgramsMap(programs);
@@ -407,29 +532,44 @@ mod token_swap {
             &programs_map,
         );
 
-        let normal_token_account = SeahorseAccount {
+        let normal_token_account = SeahorseInterfaceAccount {
             account: &ctx.accounts.normal_token_account,
             programs: &programs_map,
         };
 
-        let premium_account = SeahorseAccount {
+        let premium_account = SeahorseInterfaceAccount {
             account: &ctx.accounts.premium_account,
             programs: &programs_map,
         };
 
-        let source = SeahorseAccount {
+        let source = SeahorseInterfaceAccount {
             account: &ctx.accounts.source,
             programs: &programs_map,
         };
 
-        let destination = SeahorseAccount {
+        let destination = SeahorseInterfaceAccount {
             account: &ctx.accounts.destination,
             programs: &programs_map,
         };
 
+        let fee_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.fee_account,
+            programs: &programs_map,
+        };
+
         let clock = &ctx.accounts.clock.clone();
 
-        swap_premium_tokens_for_normal_tokens_handler(
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let normal_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_mint,
+            programs: &programs_map,
+        };
+
+        let event = swap_premium_tokens_for_normal_tokens_handler(
             source_authority.clone(),
             premium_mint_reserve_acc.clone(),
             normal_mint_reserve_acc.clone(),
@@ -437,41 +577,88 @@ mod token_swap {
             premium_account.clone(),
             source.clone(),
             destination.clone(),
+            fee_account.clone(),
+            premium_mint.clone(),
+            normal_mint.clone(),
             clock.clone(),
             amount,
-        );
+            min_amount_out,
+            deadline,
+        )?;
 
         dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
 
         dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
 
+        emit_cpi!(event);
+
         return Ok(());
     }
 
+    #[event_cpi]
     #[derive(Accounts)]
-    # [instruction (go_live_ts : i64 , initialization_ts : i64)]
+    // Shared by both versions of the instruction, so its constraints must not
+    // depend on the instruction arguments.
     pub struct CreateNormalMintReserve<'info> {
         #[account(mut)]
         pub payer: Signer<'info>,
-        # [account (init , payer = payer , seeds = ["normal-token-account" . as_bytes () . as_ref ()] , bump , token :: mint = normal_mint , token :: authority = normal_mint_reserve_acc)]
-        pub normal_token_account: Box<Account<'info, TokenAccount>>,
-        # [account (init , space = std :: mem :: size_of :: < dot :: program :: NormalMintReserve > () + 8 , payer = payer , seeds = ["normal-mint-reserve" . as_bytes () . as_ref ()] , bump)]
+        # [account (init , payer = payer , seeds = ["normal-token-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint . key () . as_ref ()] , bump , token :: mint = normal_mint , token :: authority = normal_mint_reserve_acc)]
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (init , payer = payer , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint . key () . as_ref ()] , bump , token :: mint = normal_mint , token :: authority = premium_mint_reserve_acc)]
+        pub normal_fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (init , space = dot :: program :: NormalMintReserve :: SPACE , payer = payer , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint . key () . as_ref ()] , bump)]
         pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
         #[account(mut)]
         pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (constraint = premium_mint . key () == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidPremiumMint)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
         #[account(mut)]
-        pub normal_mint: Box<Account<'info, Mint>>,
+        pub normal_mint: Box<InterfaceAccount<'info, Mint>>,
         #[account(mut)]
         pub clock: Sysvar<'info, Clock>,
         pub rent: Sysvar<'info, Rent>,
-        pub token_program: Program<'info, Token>,
+        pub token_program: Interface<'info, TokenInterface>,
         pub system_program: Program<'info, System>,
     }
 
+    /// Deprecated: `initialization_ts` is ignored. Use `create_normal_mint_reserve_v2`.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_normal_mint_reserve(
         ctx: Context<CreateNormalMintReserve>,
         go_live_ts: i64,
         initialization_ts: i64,
+        rate_numerator: u64,
+        rate_denominator: u64,
+        rounding: dot::program::RoundingPolicy,
+        fee_bps_override: Option<u16>,
+        end_ts: Option<i64>,
+        sunset_ts: Option<i64>,
+        grace_direction: dot::program::SwapDirection,
+    ) -> Result<()> {
+        return create_normal_mint_reserve_v2(
+            ctx,
+            go_live_ts,
+            rate_numerator,
+            rate_denominator,
+            rounding,
+            fee_bps_override,
+            end_ts,
+            sunset_ts,
+            grace_direction,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_normal_mint_reserve_v2(
+        ctx: Context<CreateNormalMintReserve>,
+        go_live_ts: i64,
+        rate_numerator: u64,
+        rate_denominator: u64,
+        rounding: dot::program::RoundingPolicy,
+        fee_bps_override: Option<u16>,
+        end_ts: Option<i64>,
+        sunset_ts: Option<i64>,
+        grace_direction: dot::program::SwapDirection,
     ) -> Result<()> {
         let mut programs = HashMap::new();
 
@@ -492,7 +679,7 @@ mod token_swap {
         };
 
         let normal_token_account = Empty {
-            account: SeahorseAccount {
+            account: SeahorseInterfaceAccount {
                 account: &ctx.accounts.normal_token_account,
                 programs: &programs_map,
             },
@@ -512,54 +699,78 @@ mod token_swap {
             &programs_map,
         );
 
-        let normal_mint = SeahorseAccount {
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let normal_mint = SeahorseInterfaceAccount {
             account: &ctx.accounts.normal_mint,
             programs: &programs_map,
         };
 
         let clock = &ctx.accounts.clock.clone();
 
-        create_normal_mint_reserve_handler(
+        let event = create_normal_mint_reserve_handler(
             payer.clone(),
             normal_token_account.clone(),
             normal_mint_reserve_acc.clone(),
             premium_mint_reserve_acc.clone(),
+            premium_mint.clone(),
             normal_mint.clone(),
             go_live_ts,
-            initialization_ts,
             clock.clone(),
-        );
+            rate_numerator,
+            rate_denominator,
+            rounding,
+            fee_bps_override,
+            end_ts,
+            sunset_ts,
+            grace_direction,
+        )?;
 
         dot::program::NormalMintReserve::store(normal_mint_reserve_acc.account);
 
         dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
 
+        emit_cpi!(event);
+
         return Ok(());
     }
 
+    #[event_cpi]
     #[derive(Accounts)]
-    # [instruction (go_live_timestamp : i64 , normal_mints : u32 , random_hash : String)]
+    # [instruction (go_live_timestamp : i64 , normal_mints : u32 , random_hash : String , fee_bps : u16 , end_ts : Option < i64 > , sunset_ts : Option < i64 > , grace_direction : dot :: program :: SwapDirection , supply_mode : dot :: program :: SupplyMode , withdrawal_delay : i64)]
     pub struct CreatePremiumMintReserve<'info> {
         #[account(mut)]
         pub payer: Signer<'info>,
-        # [account (init , space = std :: mem :: size_of :: < dot :: program :: PremiumMintReserve > () + 8 , payer = payer , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint . key () . as_ref () , random_hash . as_bytes () . as_ref ()] , bump)]
+        # [account (init , space = dot :: program :: PremiumMintReserve :: SPACE , payer = payer , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint . key () . as_ref () , dot :: program :: random_hash_seed (& random_hash) ?] , bump)]
         pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
         #[account(mut)]
-        pub premium_mint: Box<Account<'info, Mint>>,
-        # [account (init , payer = payer , seeds = ["premium-tokens" . as_bytes () . as_ref ()] , bump , token :: mint = premium_mint , token :: authority = premium_mint_reserve_acc)]
-        pub premium_account: Box<Account<'info, TokenAccount>>,
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (init , payer = payer , seeds = ["premium-tokens" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref ()] , bump , token :: mint = premium_mint , token :: authority = premium_mint_reserve_acc)]
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (init , payer = payer , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , premium_mint . key () . as_ref ()] , bump , token :: mint = premium_mint , token :: authority = premium_mint_reserve_acc)]
+        pub premium_fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
         #[account(mut)]
         pub clock: Sysvar<'info, Clock>,
-        pub token_program: Program<'info, Token>,
+        pub token_program: Interface<'info, TokenInterface>,
         pub system_program: Program<'info, System>,
         pub rent: Sysvar<'info, Rent>,
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_premium_mint_reserve(
         ctx: Context<CreatePremiumMintReserve>,
         go_live_timestamp: i64,
         normal_mints: u32,
         random_hash: String,
+        fee_bps: u16,
+        end_ts: Option<i64>,
+        sunset_ts: Option<i64>,
+        grace_direction: dot::program::SwapDirection,
+        supply_mode: dot::program::SupplyMode,
+        withdrawal_delay: i64,
     ) -> Result<()> {
         let mut programs = HashMap::new();
 
@@ -587,13 +798,13 @@ mod token_swap {
             bump: ctx.bumps.get("premium_mint_reserve_acc").map(|bump| *bump),
         };
 
-        let premium_mint = SeahorseAccount {
+        let premium_mint = SeahorseInterfaceAccount {
             account: &ctx.accounts.premium_mint,
             programs: &programs_map,
         };
 
         let premium_account = Empty {
-            account: SeahorseAccount {
+            account: SeahorseInterfaceAccount {
                 account: &ctx.accounts.premium_account,
                 programs: &programs_map,
             },
@@ -602,7 +813,7 @@ mod token_swap {
 
         let clock = &ctx.accounts.clock.clone();
 
-        create_premium_mint_reserve_handler(
+        let event = create_premium_mint_reserve_handler(
             payer.clone(),
             premium_mint_reserve_acc.clone(),
             premium_mint.clone(),
@@ -611,10 +822,1805 @@ mod token_swap {
             clock.clone(),
             normal_mints,
             random_hash,
-        );
+            fee_bps,
+            end_ts,
+            sunset_ts,
+            grace_direction,
+            supply_mode,
+            withdrawal_delay,
+        )?;
 
         dot::program::PremiumMintReserve::store(premium_mint_reserve_acc.account);
 
+        emit_cpi!(event);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (withdrawal_delay : i64)]
+    pub struct MigratePremiumAccount<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[doc = "CHECK: Legacy layout, verified and upgraded by the handler."]
+        #[account(mut)]
+        pub premium_mint_reserve_acc: UncheckedAccount<'info>,
+        #[account(mut)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (mut , seeds = ["premium-tokens" . as_bytes () . as_ref ()] , bump)]
+        pub legacy_premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (init , payer = authority , seeds = ["premium-tokens" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref ()] , bump , token :: mint = premium_mint , token :: authority = premium_mint_reserve_acc)]
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (init , payer = authority , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , premium_mint . key () . as_ref ()] , bump , token :: mint = premium_mint , token :: authority = premium_mint_reserve_acc)]
+        pub premium_fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        pub token_program: Interface<'info, TokenInterface>,
+        pub system_program: Program<'info, System>,
+        pub rent: Sysvar<'info, Rent>,
+    }
+
+    pub fn migrate_premium_account(
+        ctx: Context<MigratePremiumAccount>,
+        withdrawal_delay: i64,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "token_program",
+            ctx.accounts.token_program.to_account_info(),
+        );
+
+        programs.insert(
+            "system_program",
+            ctx.accounts.system_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = ctx.accounts.premium_mint_reserve_acc.to_account_info();
+
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let legacy_premium_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.legacy_premium_account,
+            programs: &programs_map,
+        };
+
+        let premium_account = Empty {
+            account: SeahorseInterfaceAccount {
+                account: &ctx.accounts.premium_account,
+                programs: &programs_map,
+            },
+            bump: ctx.bumps.get("premium_account").map(|bump| *bump),
+        };
+
+        migrate_premium_account_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            premium_mint.clone(),
+            legacy_premium_account.clone(),
+            premium_account.clone(),
+            withdrawal_delay,
+        )?;
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    pub struct MigrateNormalMintReserve<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        #[doc = "CHECK: Legacy layout, verified and deserialized by the handler."]
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref ()] , bump)]
+        pub legacy_normal_mint_reserve_acc: UncheckedAccount<'info>,
+        # [account (mut , seeds = ["normal-token-account" . as_bytes () . as_ref ()] , bump)]
+        pub legacy_normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (init , space = dot :: program :: NormalMintReserve :: SPACE , payer = authority , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint . key () . as_ref ()] , bump)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        # [account (init , payer = authority , seeds = ["normal-token-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint . key () . as_ref ()] , bump , token :: mint = normal_mint , token :: authority = normal_mint_reserve_acc)]
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (init , payer = authority , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint . key () . as_ref ()] , bump , token :: mint = normal_mint , token :: authority = premium_mint_reserve_acc)]
+        pub normal_fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (constraint = premium_mint . key () == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidPremiumMint)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        #[account(mut)]
+        pub normal_mint: Box<InterfaceAccount<'info, Mint>>,
+        pub token_program: Interface<'info, TokenInterface>,
+        pub system_program: Program<'info, System>,
+        pub rent: Sysvar<'info, Rent>,
+    }
+
+    pub fn migrate_normal_mint_reserve(ctx: Context<MigrateNormalMintReserve>) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "token_program",
+            ctx.accounts.token_program.to_account_info(),
+        );
+
+        programs.insert(
+            "system_program",
+            ctx.accounts.system_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let legacy_normal_mint_reserve_acc = ctx
+            .accounts
+            .legacy_normal_mint_reserve_acc
+            .to_account_info();
+
+        let legacy_normal_token_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.legacy_normal_token_account,
+            programs: &programs_map,
+        };
+
+        let normal_mint_reserve_acc = Empty {
+            account: dot::program::NormalMintReserve::load(
+                &mut ctx.accounts.normal_mint_reserve_acc,
+                &programs_map,
+            ),
+            bump: ctx.bumps.get("normal_mint_reserve_acc").map(|bump| *bump),
+        };
+
+        let normal_token_account = Empty {
+            account: SeahorseInterfaceAccount {
+                account: &ctx.accounts.normal_token_account,
+                programs: &programs_map,
+            },
+            bump: ctx.bumps.get("normal_token_account").map(|bump| *bump),
+        };
+
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let normal_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_mint,
+            programs: &programs_map,
+        };
+
+        migrate_normal_mint_reserve_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            legacy_normal_mint_reserve_acc.clone(),
+            legacy_normal_token_account.clone(),
+            normal_mint_reserve_acc.clone(),
+            normal_token_account.clone(),
+            premium_mint.clone(),
+            normal_mint.clone(),
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        dot::program::NormalMintReserve::store(normal_mint_reserve_acc.account);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (normal_mints : u32)]
+    pub struct SetNormalMintsCap<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+    }
+
+    pub fn set_normal_mints_cap(ctx: Context<SetNormalMintsCap>, normal_mints: u32) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        set_normal_mints_cap_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            normal_mints,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[event_cpi]
+    #[derive(Accounts)]
+    # [instruction (amount : u64)]
+    pub struct DepositPremiumTokens<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump , has_one = premium_account @ dot :: program :: TokenSwapError :: InvalidPremiumTokenAccount)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        #[account(mut)]
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = source . mint == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidSourceMint)]
+        pub source: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (constraint = premium_mint . key () == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidPremiumMint)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = token_program . key () == premium_mint_reserve_acc . premium_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub token_program: Interface<'info, TokenInterface>,
+    }
+
+    pub fn deposit_premium_tokens(ctx: Context<DepositPremiumTokens>, amount: u64) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "token_program",
+            ctx.accounts.token_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let premium_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_account,
+            programs: &programs_map,
+        };
+
+        let source = SeahorseInterfaceAccount {
+            account: &ctx.accounts.source,
+            programs: &programs_map,
+        };
+
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let event = deposit_premium_tokens_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            premium_account.clone(),
+            premium_mint.clone(),
+            source.clone(),
+            amount,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        emit_cpi!(event);
+
+        return Ok(());
+    }
+
+    #[event_cpi]
+    #[derive(Accounts)]
+    # [instruction (amount : u64)]
+    pub struct DepositNormalTokens<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , has_one = normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        #[account(mut)]
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = source . mint == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidSourceMint)]
+        pub source: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (constraint = normal_mint . key () == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidNormalMint)]
+        pub normal_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = token_program . key () == normal_mint_reserve_acc . normal_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub token_program: Interface<'info, TokenInterface>,
+    }
+
+    pub fn deposit_normal_tokens(ctx: Context<DepositNormalTokens>, amount: u64) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "token_program",
+            ctx.accounts.token_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_token_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_token_account,
+            programs: &programs_map,
+        };
+
+        let source = SeahorseInterfaceAccount {
+            account: &ctx.accounts.source,
+            programs: &programs_map,
+        };
+
+        let normal_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_mint,
+            programs: &programs_map,
+        };
+
+        let event = deposit_normal_tokens_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            normal_mint_reserve_acc.clone(),
+            normal_token_account.clone(),
+            normal_mint.clone(),
+            source.clone(),
+            amount,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
+
+        emit_cpi!(event);
+
+        return Ok(());
+    }
+
+    #[event_cpi]
+    #[derive(Accounts)]
+    pub struct ReconcilePremiumMintReserve<'info> {
+        # [account (has_one = premium_account @ dot :: program :: TokenSwapError :: InvalidPremiumTokenAccount)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    }
+
+    pub fn reconcile_premium_mint_reserve(ctx: Context<ReconcilePremiumMintReserve>) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let premium_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_account,
+            programs: &programs_map,
+        };
+
+        let event = reconcile_premium_mint_reserve_handler(
+            premium_mint_reserve_acc.clone(),
+            premium_account.clone(),
+        )?;
+
+        emit_cpi!(event);
+
+        return Ok(());
+    }
+
+    #[event_cpi]
+    #[derive(Accounts)]
+    pub struct ReconcileNormalMintReserve<'info> {
+        # [account (has_one = normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    }
+
+    pub fn reconcile_normal_mint_reserve(ctx: Context<ReconcileNormalMintReserve>) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_token_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_token_account,
+            programs: &programs_map,
+        };
+
+        let event = reconcile_normal_mint_reserve_handler(
+            normal_mint_reserve_acc.clone(),
+            normal_token_account.clone(),
+        )?;
+
+        emit_cpi!(event);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    pub struct CloseNormalMintReserve<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , has_one = normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount , close = authority)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        #[account(mut)]
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump)]
+        pub normal_fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = destination . mint == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (seeds = ["approved-destination" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , destination . key () . as_ref () , premium_mint_reserve_acc . destination_epoch . to_le_bytes () . as_ref ()] , bump = approved_destination . bump)]
+        pub approved_destination: Box<Account<'info, dot::program::ApprovedDestination>>,
+        # [account (constraint = normal_mint . key () == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidNormalMint)]
+        pub normal_mint: Box<InterfaceAccount<'info, Mint>>,
+        pub clock: Sysvar<'info, Clock>,
+        # [account (constraint = token_program . key () == normal_mint_reserve_acc . normal_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub token_program: Interface<'info, TokenInterface>,
+    }
+
+    pub fn close_normal_mint_reserve(ctx: Context<CloseNormalMintReserve>) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "token_program",
+            ctx.accounts.token_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_token_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_token_account,
+            programs: &programs_map,
+        };
+
+        let normal_fee_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_fee_account,
+            programs: &programs_map,
+        };
+
+        let destination = SeahorseInterfaceAccount {
+            account: &ctx.accounts.destination,
+            programs: &programs_map,
+        };
+
+        let normal_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_mint,
+            programs: &programs_map,
+        };
+
+        let clock = &ctx.accounts.clock.clone();
+
+        close_normal_mint_reserve_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            normal_mint_reserve_acc.clone(),
+            normal_token_account.clone(),
+            normal_fee_account.clone(),
+            normal_mint.clone(),
+            destination.clone(),
+            clock.clone(),
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    pub struct ClosePremiumMintReserve<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump , has_one = premium_account @ dot :: program :: TokenSwapError :: InvalidPremiumTokenAccount , close = authority)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        #[account(mut)]
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref ()] , bump)]
+        pub premium_fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = destination . mint == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , seeds = ["approved-destination" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , destination . key () . as_ref () , premium_mint_reserve_acc . destination_epoch . to_le_bytes () . as_ref ()] , bump = approved_destination . bump , close = authority)]
+        pub approved_destination: Box<Account<'info, dot::program::ApprovedDestination>>,
+        # [account (mut , constraint = premium_mint . key () == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidPremiumMint)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        pub clock: Sysvar<'info, Clock>,
+        # [account (constraint = token_program . key () == premium_mint_reserve_acc . premium_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub token_program: Interface<'info, TokenInterface>,
+    }
+
+    pub fn close_premium_mint_reserve(ctx: Context<ClosePremiumMintReserve>) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "token_program",
+            ctx.accounts.token_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let premium_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_account,
+            programs: &programs_map,
+        };
+
+        let premium_fee_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_fee_account,
+            programs: &programs_map,
+        };
+
+        let destination = SeahorseInterfaceAccount {
+            account: &ctx.accounts.destination,
+            programs: &programs_map,
+        };
+
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let clock = &ctx.accounts.clock.clone();
+
+        close_premium_mint_reserve_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            premium_account.clone(),
+            premium_fee_account.clone(),
+            premium_mint.clone(),
+            destination.clone(),
+            clock.clone(),
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (pause_authority : Pubkey)]
+    pub struct SetPauseAuthority<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+    }
+
+    pub fn set_pause_authority(
+        ctx: Context<SetPauseAuthority>,
+        pause_authority: Pubkey,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        set_pause_authority_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            pause_authority,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (premium_to_normal_paused : bool , normal_to_premium_paused : bool)]
+    pub struct SetPremiumMintReservePaused<'info> {
+        #[account(mut)]
+        pub pause_authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+    }
+
+    pub fn set_premium_mint_reserve_paused(
+        ctx: Context<SetPremiumMintReservePaused>,
+        premium_to_normal_paused: bool,
+        normal_to_premium_paused: bool,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let pause_authority = SeahorseSigner {
+            account: &ctx.accounts.pause_authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        set_premium_mint_reserve_paused_handler(
+            pause_authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            premium_to_normal_paused,
+            normal_to_premium_paused,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (premium_to_normal_paused : bool , normal_to_premium_paused : bool)]
+    pub struct SetNormalMintReservePaused<'info> {
+        #[account(mut)]
+        pub pause_authority: Signer<'info>,
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+    }
+
+    pub fn set_normal_mint_reserve_paused(
+        ctx: Context<SetNormalMintReservePaused>,
+        premium_to_normal_paused: bool,
+        normal_to_premium_paused: bool,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let pause_authority = SeahorseSigner {
+            account: &ctx.accounts.pause_authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        set_normal_mint_reserve_paused_handler(
+            pause_authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            normal_mint_reserve_acc.clone(),
+            premium_to_normal_paused,
+            normal_to_premium_paused,
+        )?;
+
+        dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (fee_bps : u16)]
+    pub struct SetPremiumFeeBps<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+    }
+
+    pub fn set_premium_fee_bps(ctx: Context<SetPremiumFeeBps>, fee_bps: u16) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        set_premium_fee_bps_handler(authority.clone(), premium_mint_reserve_acc.clone(), fee_bps)?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (fee_bps_override : Option < u16 >)]
+    pub struct SetNormalFeeBpsOverride<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+    }
+
+    pub fn set_normal_fee_bps_override(
+        ctx: Context<SetNormalFeeBpsOverride>,
+        fee_bps_override: Option<u16>,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        set_normal_fee_bps_override_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            normal_mint_reserve_acc.clone(),
+            fee_bps_override,
+        )?;
+
+        dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (amount : u64)]
+    pub struct ClaimFees<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        # [account (seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , fee_account . mint . as_ref ()] , bump)]
+        pub fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = destination . mint == fee_account . mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (seeds = ["approved-destination" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , destination . key () . as_ref () , premium_mint_reserve_acc . destination_epoch . to_le_bytes () . as_ref ()] , bump = approved_destination . bump)]
+        pub approved_destination: Box<Account<'info, dot::program::ApprovedDestination>>,
+        # [account (constraint = mint . key () == fee_account . mint @ dot :: program :: TokenSwapError :: InvalidSourceMint)]
+        pub mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = token_program . key () == * mint . to_account_info () . owner @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub token_program: Interface<'info, TokenInterface>,
+    }
+
+    pub fn claim_fees(ctx: Context<ClaimFees>, amount: u64) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "token_program",
+            ctx.accounts.token_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let fee_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.fee_account,
+            programs: &programs_map,
+        };
+
+        let destination = SeahorseInterfaceAccount {
+            account: &ctx.accounts.destination,
+            programs: &programs_map,
+        };
+
+        let mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.mint,
+            programs: &programs_map,
+        };
+
+        claim_fees_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            fee_account.clone(),
+            mint.clone(),
+            destination.clone(),
+            amount,
+        )?;
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (new_authority : Option < Pubkey >)]
+    pub struct ProposeAuthority<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        propose_authority_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            new_authority,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    pub struct AcceptAuthority<'info> {
+        #[account(mut)]
+        pub new_authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let new_authority = SeahorseSigner {
+            account: &ctx.accounts.new_authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        accept_authority_handler(new_authority.clone(), premium_mint_reserve_acc.clone())?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (treasurer : Pubkey)]
+    pub struct SetTreasurer<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+    }
+
+    pub fn set_treasurer(ctx: Context<SetTreasurer>, treasurer: Pubkey) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        set_treasurer_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            treasurer,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    pub struct ApproveDestination<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (init , space = dot :: program :: ApprovedDestination :: SPACE , payer = authority , seeds = ["approved-destination" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , destination . key () . as_ref () , premium_mint_reserve_acc . destination_epoch . to_le_bytes () . as_ref ()] , bump)]
+        pub approved_destination: Box<Account<'info, dot::program::ApprovedDestination>>,
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        pub system_program: Program<'info, System>,
+    }
+
+    pub fn approve_destination(ctx: Context<ApproveDestination>) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "system_program",
+            ctx.accounts.system_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let approved_destination = Empty {
+            account: dot::program::ApprovedDestination::load(
+                &mut ctx.accounts.approved_destination,
+                &programs_map,
+            ),
+            bump: ctx.bumps.get("approved_destination").map(|bump| *bump),
+        };
+
+        let destination = SeahorseInterfaceAccount {
+            account: &ctx.accounts.destination,
+            programs: &programs_map,
+        };
+
+        approve_destination_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            approved_destination.clone(),
+            destination.clone(),
+        )?;
+
+        dot::program::ApprovedDestination::store(approved_destination.account);
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    pub struct RevokeDestination<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , seeds = ["approved-destination" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , approved_destination . destination . as_ref () , approved_destination . epoch . to_le_bytes () . as_ref ()] , bump = approved_destination . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , close = authority)]
+        pub approved_destination: Box<Account<'info, dot::program::ApprovedDestination>>,
+    }
+
+    pub fn revoke_destination(ctx: Context<RevokeDestination>) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        revoke_destination_handler(authority.clone(), premium_mint_reserve_acc.clone())?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (amount : u64)]
+    pub struct RequestNormalWithdrawal<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , has_one = normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (constraint = destination . mint == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (seeds = ["approved-destination" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , destination . key () . as_ref () , premium_mint_reserve_acc . destination_epoch . to_le_bytes () . as_ref ()] , bump = approved_destination . bump)]
+        pub approved_destination: Box<Account<'info, dot::program::ApprovedDestination>>,
+        # [account (init , space = dot :: program :: WithdrawalRequest :: SPACE , payer = authority , seeds = ["withdrawal-request" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_token_account . key () . as_ref ()] , bump)]
+        pub withdrawal_request: Box<Account<'info, dot::program::WithdrawalRequest>>,
+        pub clock: Sysvar<'info, Clock>,
+        pub system_program: Program<'info, System>,
+    }
+
+    pub fn request_normal_withdrawal(
+        ctx: Context<RequestNormalWithdrawal>,
+        amount: u64,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "system_program",
+            ctx.accounts.system_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_token_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_token_account,
+            programs: &programs_map,
+        };
+
+        let destination = SeahorseInterfaceAccount {
+            account: &ctx.accounts.destination,
+            programs: &programs_map,
+        };
+
+        let withdrawal_request = Empty {
+            account: dot::program::WithdrawalRequest::load(
+                &mut ctx.accounts.withdrawal_request,
+                &programs_map,
+            ),
+            bump: ctx.bumps.get("withdrawal_request").map(|bump| *bump),
+        };
+
+        let clock = &ctx.accounts.clock.clone();
+
+        request_normal_withdrawal_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            normal_token_account.clone(),
+            destination.clone(),
+            withdrawal_request.clone(),
+            clock.clone(),
+            amount,
+        )?;
+
+        dot::program::WithdrawalRequest::store(withdrawal_request.account);
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (amount : u64)]
+    pub struct RequestPremiumWithdrawal<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump , has_one = premium_account @ dot :: program :: TokenSwapError :: InvalidPremiumTokenAccount)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (constraint = destination . mint == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (seeds = ["approved-destination" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , destination . key () . as_ref () , premium_mint_reserve_acc . destination_epoch . to_le_bytes () . as_ref ()] , bump = approved_destination . bump)]
+        pub approved_destination: Box<Account<'info, dot::program::ApprovedDestination>>,
+        # [account (init , space = dot :: program :: WithdrawalRequest :: SPACE , payer = authority , seeds = ["withdrawal-request" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , premium_account . key () . as_ref ()] , bump)]
+        pub withdrawal_request: Box<Account<'info, dot::program::WithdrawalRequest>>,
+        pub clock: Sysvar<'info, Clock>,
+        pub system_program: Program<'info, System>,
+    }
+
+    pub fn request_premium_withdrawal(
+        ctx: Context<RequestPremiumWithdrawal>,
+        amount: u64,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "system_program",
+            ctx.accounts.system_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let premium_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_account,
+            programs: &programs_map,
+        };
+
+        let destination = SeahorseInterfaceAccount {
+            account: &ctx.accounts.destination,
+            programs: &programs_map,
+        };
+
+        let withdrawal_request = Empty {
+            account: dot::program::WithdrawalRequest::load(
+                &mut ctx.accounts.withdrawal_request,
+                &programs_map,
+            ),
+            bump: ctx.bumps.get("withdrawal_request").map(|bump| *bump),
+        };
+
+        let clock = &ctx.accounts.clock.clone();
+
+        request_premium_withdrawal_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            premium_account.clone(),
+            destination.clone(),
+            withdrawal_request.clone(),
+            clock.clone(),
+            amount,
+        )?;
+
+        dot::program::WithdrawalRequest::store(withdrawal_request.account);
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    pub struct CancelWithdrawal<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , seeds = ["withdrawal-request" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , withdrawal_request . vault . as_ref ()] , bump = withdrawal_request . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , close = authority)]
+        pub withdrawal_request: Box<Account<'info, dot::program::WithdrawalRequest>>,
+    }
+
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        cancel_withdrawal_handler(authority.clone(), premium_mint_reserve_acc.clone())?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (withdrawal_delay : i64)]
+    pub struct SetWithdrawalDelay<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+    }
+
+    pub fn set_withdrawal_delay(
+        ctx: Context<SetWithdrawalDelay>,
+        withdrawal_delay: i64,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        set_withdrawal_delay_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            withdrawal_delay,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (locked_until : i64)]
+    pub struct LockLiquidity<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+    }
+
+    pub fn lock_liquidity(ctx: Context<LockLiquidity>, locked_until: i64) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        lock_liquidity_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            locked_until,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (go_live_timestamp : i64 , end_ts : Option < i64 > , sunset_ts : Option < i64 >)]
+    pub struct UpdateSchedule<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        #[account(mut)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        pub clock: Sysvar<'info, Clock>,
+    }
+
+    pub fn update_schedule(
+        ctx: Context<UpdateSchedule>,
+        go_live_timestamp: i64,
+        end_ts: Option<i64>,
+        sunset_ts: Option<i64>,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let clock = &ctx.accounts.clock.clone();
+
+        update_schedule_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            clock.clone(),
+            go_live_timestamp,
+            end_ts,
+            sunset_ts,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[derive(Accounts)]
+    # [instruction (go_live_ts : i64 , end_ts : Option < i64 > , sunset_ts : Option < i64 >)]
+    pub struct UpdateNormalSchedule<'info> {
+        #[account(mut)]
+        pub authority: Signer<'info>,
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        pub clock: Sysvar<'info, Clock>,
+    }
+
+    pub fn update_normal_schedule(
+        ctx: Context<UpdateNormalSchedule>,
+        go_live_ts: i64,
+        end_ts: Option<i64>,
+        sunset_ts: Option<i64>,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+        let programs_map = ProgramsMap(programs);
+        let authority = SeahorseSigner {
+            account: &ctx.accounts.authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let clock = &ctx.accounts.clock.clone();
+
+        update_normal_schedule_handler(
+            authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            normal_mint_reserve_acc.clone(),
+            clock.clone(),
+            go_live_ts,
+            end_ts,
+            sunset_ts,
+        )?;
+
+        dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
+
+        return Ok(());
+    }
+
+    #[event_cpi]
+    #[derive(Accounts)]
+    pub struct SwapSolForPremiumTokens<'info> {
+        #[account(mut)]
+        pub source_authority: Signer<'info>,
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump , has_one = premium_account @ dot :: program :: TokenSwapError :: InvalidPremiumTokenAccount)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , has_one = normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        #[account(mut)]
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        #[account(mut)]
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (init , payer = source_authority , seeds = ["wrapped-sol" . as_bytes () . as_ref () , normal_mint_reserve_acc . key () . as_ref () , source_authority . key () . as_ref ()] , bump , token :: mint = normal_mint , token :: authority = source_authority , token :: token_program = normal_token_program)]
+        pub wrapped_sol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = destination . mint == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref ()] , bump)]
+        pub fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        #[account(mut)]
+        pub clock: Sysvar<'info, Clock>,
+        # [account (mut , constraint = premium_mint . key () == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidPremiumMint)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (mut , constraint = normal_mint . key () == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidNormalMint)]
+        pub normal_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = premium_token_program . key () == premium_mint_reserve_acc . premium_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub premium_token_program: Interface<'info, TokenInterface>,
+        # [account (constraint = normal_token_program . key () == normal_mint_reserve_acc . normal_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub normal_token_program: Interface<'info, TokenInterface>,
+        pub system_program: Program<'info, System>,
+        pub rent: Sysvar<'info, Rent>,
+    }
+
+    pub fn swap_sol_for_premium_tokens(
+        ctx: Context<SwapSolForPremiumTokens>,
+        amount: u64,
+        min_amount_out: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "premium_token_program",
+            ctx.accounts.premium_token_program.to_account_info(),
+        );
+
+        programs.insert(
+            "normal_token_program",
+            ctx.accounts.normal_token_program.to_account_info(),
+        );
+
+        programs.insert(
+            "system_program",
+            ctx.accounts.system_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let source_authority = SeahorseSigner {
+            account: &ctx.accounts.source_authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_token_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_token_account,
+            programs: &programs_map,
+        };
+
+        let premium_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_account,
+            programs: &programs_map,
+        };
+
+        let wrapped_sol_account = Empty {
+            account: SeahorseInterfaceAccount {
+                account: &ctx.accounts.wrapped_sol_account,
+                programs: &programs_map,
+            },
+            bump: ctx.bumps.get("wrapped_sol_account").map(|bump| *bump),
+        };
+
+        let destination = SeahorseInterfaceAccount {
+            account: &ctx.accounts.destination,
+            programs: &programs_map,
+        };
+
+        let fee_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.fee_account,
+            programs: &programs_map,
+        };
+
+        let clock = &ctx.accounts.clock.clone();
+
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let normal_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_mint,
+            programs: &programs_map,
+        };
+
+        let event = swap_sol_for_premium_tokens_handler(
+            source_authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            normal_mint_reserve_acc.clone(),
+            normal_token_account.clone(),
+            premium_account.clone(),
+            wrapped_sol_account.clone(),
+            destination.clone(),
+            fee_account.clone(),
+            premium_mint.clone(),
+            normal_mint.clone(),
+            clock.clone(),
+            amount,
+            min_amount_out,
+            deadline,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
+
+        emit_cpi!(event);
+
+        return Ok(());
+    }
+
+    #[event_cpi]
+    #[derive(Accounts)]
+    pub struct SwapPremiumTokensForSol<'info> {
+        #[account(mut)]
+        pub source_authority: Signer<'info>,
+        # [account (mut , seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump , has_one = premium_account @ dot :: program :: TokenSwapError :: InvalidPremiumTokenAccount)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated , has_one = normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
+        pub normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        #[account(mut)]
+        pub normal_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        #[account(mut)]
+        pub premium_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = source . mint == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidSourceMint)]
+        pub source: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (init , payer = source_authority , seeds = ["wrapped-sol" . as_bytes () . as_ref () , normal_mint_reserve_acc . key () . as_ref () , source_authority . key () . as_ref ()] , bump , token :: mint = normal_mint , token :: authority = source_authority , token :: token_program = normal_token_program)]
+        pub wrapped_sol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , normal_mint_reserve_acc . normal_mint . as_ref ()] , bump)]
+        pub fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        #[account(mut)]
+        pub clock: Sysvar<'info, Clock>,
+        # [account (mut , constraint = premium_mint . key () == premium_mint_reserve_acc . premium_mint @ dot :: program :: TokenSwapError :: InvalidPremiumMint)]
+        pub premium_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (mut , constraint = normal_mint . key () == normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidNormalMint)]
+        pub normal_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = premium_token_program . key () == premium_mint_reserve_acc . premium_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub premium_token_program: Interface<'info, TokenInterface>,
+        # [account (constraint = normal_token_program . key () == normal_mint_reserve_acc . normal_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub normal_token_program: Interface<'info, TokenInterface>,
+        pub system_program: Program<'info, System>,
+        pub rent: Sysvar<'info, Rent>,
+    }
+
+    pub fn swap_premium_tokens_for_sol(
+        ctx: Context<SwapPremiumTokensForSol>,
+        amount: u64,
+        min_amount_out: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "premium_token_program",
+            ctx.accounts.premium_token_program.to_account_info(),
+        );
+
+        programs.insert(
+            "normal_token_program",
+            ctx.accounts.normal_token_program.to_account_info(),
+        );
+
+        programs.insert(
+            "system_program",
+            ctx.accounts.system_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let source_authority = SeahorseSigner {
+            account: &ctx.accounts.source_authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let normal_token_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_token_account,
+            programs: &programs_map,
+        };
+
+        let premium_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_account,
+            programs: &programs_map,
+        };
+
+        let source = SeahorseInterfaceAccount {
+            account: &ctx.accounts.source,
+            programs: &programs_map,
+        };
+
+        let wrapped_sol_account = Empty {
+            account: SeahorseInterfaceAccount {
+                account: &ctx.accounts.wrapped_sol_account,
+                programs: &programs_map,
+            },
+            bump: ctx.bumps.get("wrapped_sol_account").map(|bump| *bump),
+        };
+
+        let fee_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.fee_account,
+            programs: &programs_map,
+        };
+
+        let clock = &ctx.accounts.clock.clone();
+
+        let premium_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.premium_mint,
+            programs: &programs_map,
+        };
+
+        let normal_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.normal_mint,
+            programs: &programs_map,
+        };
+
+        let event = swap_premium_tokens_for_sol_handler(
+            source_authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            normal_mint_reserve_acc.clone(),
+            normal_token_account.clone(),
+            premium_account.clone(),
+            source.clone(),
+            wrapped_sol_account.clone(),
+            fee_account.clone(),
+            premium_mint.clone(),
+            normal_mint.clone(),
+            clock.clone(),
+            amount,
+            min_amount_out,
+            deadline,
+        )?;
+
+        dot::program::PremiumMintReserve::store(premium_mint_reserve_acc);
+
+        dot::program::NormalMintReserve::store(normal_mint_reserve_acc);
+
+        emit_cpi!(event);
+
+        return Ok(());
+    }
+
+    #[event_cpi]
+    #[derive(Accounts)]
+    pub struct SwapNormalForNormal<'info> {
+        #[account(mut)]
+        pub source_authority: Signer<'info>,
+        # [account (seeds = ["premium-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . premium_mint . as_ref () , premium_mint_reserve_acc . random_hash . as_bytes () . as_ref ()] , bump = premium_mint_reserve_acc . bump)]
+        pub premium_mint_reserve_acc: Box<Account<'info, dot::program::PremiumMintReserve>>,
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , from_normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = from_normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated)]
+        pub from_normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        # [account (mut , seeds = ["normal-mint-reserve" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , to_normal_mint_reserve_acc . normal_mint . as_ref ()] , bump = to_normal_mint_reserve_acc . bump , has_one = premium_mint_reserve_acc @ dot :: program :: TokenSwapError :: ReservesNotRelated)]
+        pub to_normal_mint_reserve_acc: Box<Account<'info, dot::program::NormalMintReserve>>,
+        # [account (mut , constraint = from_token_account . key () == from_normal_mint_reserve_acc . normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
+        pub from_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = to_token_account . key () == to_normal_mint_reserve_acc . normal_token_account @ dot :: program :: TokenSwapError :: InvalidNormalTokenAccount)]
+        pub to_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = source . mint == from_normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidSourceMint)]
+        pub source: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , constraint = destination . mint == to_normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidDestinationMint)]
+        pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+        # [account (mut , seeds = ["fee-account" . as_bytes () . as_ref () , premium_mint_reserve_acc . key () . as_ref () , to_normal_mint_reserve_acc . normal_mint . as_ref ()] , bump)]
+        pub fee_account: Box<InterfaceAccount<'info, TokenAccount>>,
+        #[account(mut)]
+        pub clock: Sysvar<'info, Clock>,
+        # [account (mut , constraint = from_mint . key () == from_normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidNormalMint)]
+        pub from_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = to_mint . key () == to_normal_mint_reserve_acc . normal_mint @ dot :: program :: TokenSwapError :: InvalidNormalMint)]
+        pub to_mint: Box<InterfaceAccount<'info, Mint>>,
+        # [account (constraint = from_token_program . key () == from_normal_mint_reserve_acc . normal_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub from_token_program: Interface<'info, TokenInterface>,
+        # [account (constraint = to_token_program . key () == to_normal_mint_reserve_acc . normal_token_program @ dot :: program :: TokenSwapError :: InvalidTokenProgram)]
+        pub to_token_program: Interface<'info, TokenInterface>,
+    }
+
+    pub fn swap_normal_for_normal(
+        ctx: Context<SwapNormalForNormal>,
+        amount: u64,
+        min_amount_out: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        let mut programs = HashMap::new();
+
+        programs.insert(
+            "from_token_program",
+            ctx.accounts.from_token_program.to_account_info(),
+        );
+
+        programs.insert(
+            "to_token_program",
+            ctx.accounts.to_token_program.to_account_info(),
+        );
+
+        let programs_map = ProgramsMap(programs);
+        let source_authority = SeahorseSigner {
+            account: &ctx.accounts.source_authority,
+            programs: &programs_map,
+        };
+
+        let premium_mint_reserve_acc = dot::program::PremiumMintReserve::load(
+            &mut ctx.accounts.premium_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let from_normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.from_normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let to_normal_mint_reserve_acc = dot::program::NormalMintReserve::load(
+            &mut ctx.accounts.to_normal_mint_reserve_acc,
+            &programs_map,
+        );
+
+        let from_token_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.from_token_account,
+            programs: &programs_map,
+        };
+
+        let to_token_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.to_token_account,
+            programs: &programs_map,
+        };
+
+        let source = SeahorseInterfaceAccount {
+            account: &ctx.accounts.source,
+            programs: &programs_map,
+        };
+
+        let destination = SeahorseInterfaceAccount {
+            account: &ctx.accounts.destination,
+            programs: &programs_map,
+        };
+
+        let fee_account = SeahorseInterfaceAccount {
+            account: &ctx.accounts.fee_account,
+            programs: &programs_map,
+        };
+
+        let clock = &ctx.accounts.clock.clone();
+
+        let from_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.from_mint,
+            programs: &programs_map,
+        };
+
+        let to_mint = SeahorseInterfaceAccount {
+            account: &ctx.accounts.to_mint,
+            programs: &programs_map,
+        };
+
+        let event = swap_normal_for_normal_handler(
+            source_authority.clone(),
+            premium_mint_reserve_acc.clone(),
+            from_normal_mint_reserve_acc.clone(),
+            to_normal_mint_reserve_acc.clone(),
+            from_token_account.clone(),
+            to_token_account.clone(),
+            source.clone(),
+            destination.clone(),
+            fee_account.clone(),
+            from_mint.clone(),
+            to_mint.clone(),
+            clock.clone(),
+            amount,
+            min_amount_out,
+            deadline,
+        )?;
+
+        dot::program::NormalMintReserve::store(from_normal_mint_reserve_acc);
+
+        dot::program::NormalMintReserve::store(to_normal_mint_reserve_acc);
+
+        emit_cpi!(event);
+
         return Ok(());
     }
 }
@@ -1,16 +1,90 @@
-import * as anchor from "@project-serum/anchor";
-import { Program } from "@project-serum/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { createMint, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey, SystemProgram, SYSVAR_CLOCK_PUBKEY, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { assert } from "chai";
 import { TokenSwap } from "../target/types/token_swap";
 
 describe("token_swap", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.TokenSwap as Program<TokenSwap>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+  const [eventAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("__event_authority")],
+    program.programId
+  );
+
+  async function createPremiumMintReserve(randomHash: string) {
+    const premiumMint = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+    // Seeds are capped at 32 bytes; longer hashes get an address derived from
+    // their prefix so the request reaches the program's own length check.
+    const [premiumMintReserveAcc] = PublicKey.findProgramAddressSync(
+      [Buffer.from("premium-reserve"), premiumMint.toBuffer(), Buffer.from(randomHash).subarray(0, 32)],
+      program.programId
+    );
+    const [premiumAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("premium-tokens"), premiumMintReserveAcc.toBuffer()],
+      program.programId
+    );
+    const [premiumFeeAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee-account"), premiumMintReserveAcc.toBuffer(), premiumMint.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createPremiumMintReserve(
+        new anchor.BN(0),
+        1,
+        randomHash,
+        0,
+        null,
+        null,
+        { premiumToNormal: {} },
+        { vault: {} },
+        new anchor.BN(24 * 60 * 60)
+      )
+      .accounts({
+        payer: payer.publicKey,
+        premiumMintReserveAcc,
+        premiumMint,
+        premiumAccount,
+        premiumFeeAccount,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+        eventAuthority,
+        program: program.programId,
+      })
+      .rpc();
+
+    return { premiumMint, premiumMintReserveAcc, premiumAccount };
+  }
+
+  it("creates a premium mint reserve", async () => {
+    const { premiumMint, premiumMintReserveAcc, premiumAccount } =
+      await createPremiumMintReserve("reserve-a");
+
+    const reserve = await program.account.premiumMintReserve.fetch(premiumMintReserveAcc);
+    assert.ok(reserve.premiumMint.equals(premiumMint));
+    assert.ok(reserve.premiumAccount.equals(premiumAccount));
+    assert.ok(reserve.creator.equals(payer.publicKey));
+    assert.ok(reserve.treasurer.equals(payer.publicKey));
+    assert.equal(reserve.linkedNormalMints, 0);
+    assert.equal(reserve.withdrawalDelay.toNumber(), 24 * 60 * 60);
+    assert.equal(reserve.randomHash, "reserve-a");
+  });
+
+  it("rejects a random hash longer than a PDA seed", async () => {
+    try {
+      await createPremiumMintReserve("x".repeat(33));
+      assert.fail("expected RandomHashTooLong");
+    } catch (err) {
+      assert.include(String(err), "RandomHashTooLong");
+    }
   });
 });